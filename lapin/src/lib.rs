@@ -24,7 +24,7 @@
 mod config;
 
 use deadpool::{async_trait, managed};
-use lapin::{ConnectionProperties, Error};
+use lapin::{protocol::constants::REPLY_SUCCESS, ConnectionProperties, Error};
 
 pub use lapin;
 
@@ -98,4 +98,9 @@ impl managed::Manager for Manager {
             ))),
         }
     }
+
+    async fn destroy(&self, conn: lapin::Connection) {
+        // Errors are ignored as the connection is discarded anyway.
+        let _ = conn.close(REPLY_SUCCESS, "OK").await;
+    }
 }
@@ -152,6 +152,16 @@ impl managed::Manager for Manager {
     fn detach(&self, object: &mut ClientWrapper) {
         self.statement_caches.detach(&object.statement_cache);
     }
+
+    async fn destroy(&self, mut client: ClientWrapper) {
+        // Dropping the client without aborting the connection task makes
+        // the connection send a `Terminate` message before shutting down.
+        let conn_task = client.conn_task.take();
+        drop(client);
+        if let Some(conn_task) = conn_task {
+            let _ = conn_task.await;
+        }
+    }
 }
 
 #[async_trait]
@@ -371,7 +381,7 @@ pub struct ClientWrapper {
 
     /// A handle to the connection task that should be aborted when the client
     /// wrapper is dropped.
-    conn_task: JoinHandle<()>,
+    conn_task: Option<JoinHandle<()>>,
 
     /// [`StatementCache`] of this client.
     pub statement_cache: Arc<StatementCache>,
@@ -384,7 +394,7 @@ impl ClientWrapper {
     pub fn new(client: PgClient, conn_task: JoinHandle<()>) -> Self {
         Self {
             client,
-            conn_task,
+            conn_task: Some(conn_task),
             statement_cache: Arc::new(StatementCache::new()),
         }
     }
@@ -443,7 +453,9 @@ impl DerefMut for ClientWrapper {
 
 impl Drop for ClientWrapper {
     fn drop(&mut self) {
-        self.conn_task.abort()
+        if let Some(conn_task) = &self.conn_task {
+            conn_task.abort()
+        }
     }
 }
 
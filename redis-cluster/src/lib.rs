@@ -64,6 +64,21 @@ impl Connection {
     pub fn take(this: Self) -> RedisConnection {
         Object::take(this.conn)
     }
+
+    /// Marks this [`Connection`] as broken so it is discarded instead of
+    /// being returned to its [`Pool`] when being dropped.
+    ///
+    /// See [`Object::mark_broken()`] for details.
+    pub fn mark_broken(this: &mut Self) {
+        Object::mark_broken(&mut this.conn)
+    }
+
+    /// Indicates whether this [`Connection`] has been marked as broken using
+    /// [`Connection::mark_broken()`].
+    #[must_use]
+    pub fn is_broken(this: &Self) -> bool {
+        Object::is_broken(&this.conn)
+    }
 }
 
 impl From<Object> for Connection {
@@ -59,6 +59,56 @@ impl Runtime {
         }
     }
 
+    /// Waits until the specified `duration` has elapsed.
+    #[allow(unused_variables)]
+    pub async fn sleep(&self, duration: Duration) {
+        match self {
+            #[cfg(feature = "tokio_1")]
+            Self::Tokio1 => tokio_1::time::sleep(duration).await,
+            #[cfg(feature = "async-std_1")]
+            Self::AsyncStd1 => async_std_1::task::sleep(duration).await,
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    /// Spawns the given [`Future`] as a background task.
+    ///
+    /// The task is detached and runs until the `future` completes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on a thread where [`Runtime::can_spawn()`] returns
+    /// `false`.
+    #[allow(unused_variables)]
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        match self {
+            #[cfg(feature = "tokio_1")]
+            Self::Tokio1 => drop(tokio_1::spawn(future)),
+            #[cfg(feature = "async-std_1")]
+            Self::AsyncStd1 => drop(async_std_1::task::spawn(future)),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    /// Indicates whether [`Runtime::spawn()`] can be called on the current
+    /// thread. The [`tokio` 1.0](tokio_1) runtime can only spawn tasks from
+    /// within its context.
+    pub fn can_spawn(&self) -> bool {
+        match self {
+            #[cfg(feature = "tokio_1")]
+            Self::Tokio1 => tokio_1::runtime::Handle::try_current().is_ok(),
+            #[cfg(feature = "async-std_1")]
+            Self::AsyncStd1 => true,
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
     /// Runs the given closure on a thread where blocking is acceptable.
     ///
     /// # Errors
@@ -29,11 +29,16 @@ pub mod managed;
 #[cfg_attr(docsrs, doc(cfg(feature = "unmanaged")))]
 pub mod unmanaged;
 
+#[cfg(any(feature = "managed", feature = "unmanaged"))]
+mod stats;
+
 // For handy re-usage in integration crates.
 #[cfg(feature = "managed")]
 #[doc(hidden)]
 pub use async_trait::async_trait;
 
+use std::time::Duration;
+
 pub use deadpool_runtime::{Runtime, SpawnBlockingError};
 
 /// The current pool status.
@@ -43,11 +48,107 @@ pub struct Status {
     pub max_size: usize,
 
     /// The current size of the pool.
+    ///
+    /// This is the sum of [`Status::available`], [`Status::in_use`],
+    /// [`Status::creating`] and [`Status::recycling`].
     pub size: usize,
 
-    /// The number of available objects in the pool.
+    /// The number of idle objects in the pool.
     pub available: usize,
 
+    /// The number of objects currently handed out by the pool.
+    pub in_use: usize,
+
+    /// The number of objects currently being created.
+    ///
+    /// This is always `0` for unmanaged pools.
+    pub creating: usize,
+
+    /// The number of objects currently being recycled.
+    ///
+    /// This is always `0` for unmanaged pools.
+    pub recycling: usize,
+
     /// The number of futures waiting for an object.
     pub waiting: usize,
+
+    /// The state of the circuit breaker of the pool.
+    ///
+    /// Pools without a circuit breaker always report
+    /// [`CircuitState::Closed`].
+    pub circuit: CircuitState,
+}
+
+/// The state of the circuit breaker of a pool.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircuitState {
+    /// Objects are created as usual.
+    Closed,
+
+    /// Creating objects failed repeatedly. Attempts to create new objects
+    /// fail immediately until the cool-down period is over.
+    Open,
+
+    /// The cool-down period is over and a single attempt to create a new
+    /// object is let through in order to probe the backend.
+    HalfOpen,
+}
+
+/// Cumulative statistics of a pool.
+///
+/// Unlike the [`Status`] all counters start at `0` when the pool is created
+/// and only ever increase, which makes them suitable for computing rates.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Stats {
+    /// The number of objects which have been created.
+    ///
+    /// This is always `0` for unmanaged pools.
+    pub created: u64,
+
+    /// The number of failed attempts to create an object including the ones
+    /// which timed out.
+    ///
+    /// This is always `0` for unmanaged pools.
+    pub create_errors: u64,
+
+    /// The number of objects which have been recycled successfully.
+    ///
+    /// This is always `0` for unmanaged pools.
+    pub recycled: u64,
+
+    /// The number of objects which failed to be recycled.
+    ///
+    /// This is always `0` for unmanaged pools.
+    pub recycle_errors: u64,
+
+    /// The number of objects which have been detached from the pool, e.g.
+    /// because they failed to be recycled, expired or were marked as broken
+    /// via `Object::mark_broken()`. Objects removed via
+    /// `Object::take()` are counted by [`Stats::taken`] instead.
+    ///
+    /// This is always `0` for unmanaged pools.
+    pub detached: u64,
+
+    /// The number of `Object::take()` calls.
+    pub taken: u64,
+
+    /// The number of timeouts while waiting for an object.
+    pub wait_timeouts: u64,
+
+    /// The number of timeouts while creating an object.
+    ///
+    /// This is always `0` for unmanaged pools.
+    pub create_timeouts: u64,
+
+    /// The number of timeouts while recycling an object.
+    ///
+    /// This is always `0` for unmanaged pools.
+    pub recycle_timeouts: u64,
+
+    /// The total time spent waiting for objects.
+    pub wait_time: Duration,
+
+    /// The longest time spent waiting for an object.
+    pub max_wait_time: Duration,
 }
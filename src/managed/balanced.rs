@@ -0,0 +1,318 @@
+//! Pool spreading its objects over multiple endpoints.
+
+use std::{
+    fmt,
+    future::Future,
+    panic::Location,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use deadpool_runtime::Runtime;
+
+use super::{
+    builder::check_config, BalanceStrategy, BalancedPoolConfig, BuildError, CircuitState, Manager,
+    Object, Pool, PoolBuilder, PoolConfig, PoolError, Priority, Status, TimeoutType,
+};
+
+/// Name of [`BalancedPool`]s which haven't been named using
+/// [`BalancedPoolBuilder::name()`].
+const DEFAULT_NAME: &str = "balanced";
+
+/// Pool spreading its objects over multiple endpoints, e.g. the read replicas
+/// of a database or the nodes of a cache cluster.
+///
+/// Every endpoint is backed by its own [`Pool`] using the [`Manager`] passed
+/// to [`BalancedPoolBuilder::endpoint()`] and the
+/// [`BalancedPoolConfig::pool`] configuration. The endpoint to retrieve an
+/// object from is chosen by the [`BalancedPoolConfig::strategy`].
+///
+/// An endpoint is considered unhealthy while the circuit breaker around its
+/// [`Manager::create()`] is open (see [`PoolConfig::circuit_breaker`]).
+/// Unhealthy endpoints are skipped and retrieving an object fails over to
+/// the next endpoint if creating an object fails.
+///
+/// This struct can be cloned and transferred across thread boundaries and
+/// uses reference counting for its internal state.
+pub struct BalancedPool<M: Manager> {
+    inner: Arc<BalancedPoolInner<M>>,
+}
+
+// Implemented manually to avoid unnecessary trait bound on `M` type parameter.
+impl<M: Manager> fmt::Debug for BalancedPool<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BalancedPool")
+            .field("name", &self.inner.name)
+            .field("config", &self.inner.config)
+            .field("endpoints", &self.inner.endpoints.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<M: Manager> Clone for BalancedPool<M> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<M: Manager + 'static> BalancedPool<M> {
+    /// Instantiates a builder for a new [`BalancedPool`].
+    pub fn builder() -> BalancedPoolBuilder<M> {
+        BalancedPoolBuilder {
+            endpoints: Vec::new(),
+            name: None,
+            config: BalancedPoolConfig::default(),
+            runtime: None,
+        }
+    }
+
+    /// Retrieves an [`Object`] from one of the endpoints of this
+    /// [`BalancedPool`] or waits for one to become available.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details. If creating an object fails for all
+    /// endpoints the error of the last one is returned.
+    #[track_caller]
+    pub fn get(&self) -> impl Future<Output = Result<Object<M>, PoolError<M::Error>>> + '_ {
+        let location = Location::caller();
+        async move {
+            let mut result = Err(PoolError::Closed);
+            for index in self.inner.candidates() {
+                let pool = &self.inner.endpoints[index].pool;
+                result = pool
+                    .checkout(pool.timeouts(), Priority::Normal, location)
+                    .await;
+                match &result {
+                    Err(PoolError::Backend(_))
+                    | Err(PoolError::CircuitOpen)
+                    | Err(PoolError::Timeout(TimeoutType::Create)) => continue,
+                    _ => break,
+                }
+            }
+            result
+        }
+    }
+
+    /// Retrieves the [`Status`] of all endpoints in the order they were
+    /// added. An endpoint is unhealthy if its [`Status::circuit`] is
+    /// [`CircuitState::Open`].
+    #[must_use]
+    pub fn statuses(&self) -> Vec<Status> {
+        self.inner
+            .endpoints
+            .iter()
+            .map(|endpoint| endpoint.pool.status())
+            .collect()
+    }
+
+    /// Returns the name of this [`BalancedPool`], see
+    /// [`BalancedPoolBuilder::name()`].
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.inner.name
+    }
+
+    /// Returns the [`BalancedPoolConfig`] of this [`BalancedPool`].
+    #[must_use]
+    pub fn config(&self) -> &BalancedPoolConfig {
+        &self.inner.config
+    }
+}
+
+/// Builder for [`BalancedPool`]s.
+///
+/// Instances of this are created by calling the [`BalancedPool::builder()`]
+/// method.
+#[must_use = "builder does nothing itself, use `.build()` to build it"]
+pub struct BalancedPoolBuilder<M: Manager> {
+    endpoints: Vec<(M, u32)>,
+    name: Option<String>,
+    config: BalancedPoolConfig,
+    runtime: Option<Runtime>,
+}
+
+// Implemented manually to avoid unnecessary trait bound on `M` type parameter.
+impl<M: Manager> fmt::Debug for BalancedPoolBuilder<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BalancedPoolBuilder")
+            .field("endpoints", &self.endpoints.len())
+            .field("name", &self.name)
+            .field("config", &self.config)
+            .field("runtime", &self.runtime)
+            .finish()
+    }
+}
+
+impl<M: Manager + 'static> BalancedPoolBuilder<M> {
+    /// Builds the [`BalancedPool`].
+    ///
+    /// # Errors
+    ///
+    /// See [`BuildError`] for details.
+    pub fn build(self) -> Result<BalancedPool<M>, BuildError> {
+        if self.endpoints.is_empty() {
+            return Err(BuildError::NoEndpoints);
+        }
+        check_config(&self.config.pool, self.runtime)?;
+        let (config, runtime) = (self.config, self.runtime);
+        let name = self.name.unwrap_or_else(|| DEFAULT_NAME.to_owned());
+        let endpoints = self
+            .endpoints
+            .into_iter()
+            .enumerate()
+            .map(|(index, (manager, weight))| {
+                let mut builder = PoolBuilder::new(manager);
+                builder.name = Some(format!("{}[{}]", name, index));
+                builder.config = config.pool;
+                builder.runtime = runtime;
+                Endpoint {
+                    pool: Pool::from_builder(builder),
+                    weight,
+                }
+            })
+            .collect::<Vec<_>>();
+        Ok(BalancedPool {
+            inner: Arc::new(BalancedPoolInner {
+                current_weights: Mutex::new(vec![0; endpoints.len()]),
+                endpoints,
+                name,
+                config,
+                next: AtomicUsize::new(0),
+            }),
+        })
+    }
+
+    /// Adds an endpoint with a weight of `1`.
+    pub fn endpoint(self, manager: M) -> Self {
+        self.weighted_endpoint(manager, 1)
+    }
+
+    /// Adds an endpoint with the given `weight` which is used by the
+    /// [`BalanceStrategy::Weighted`] strategy.
+    pub fn weighted_endpoint(mut self, manager: M, weight: u32) -> Self {
+        self.endpoints.push((manager, weight));
+        self
+    }
+
+    /// Sets the name of the [`BalancedPool`].
+    ///
+    /// The sub-pool of every endpoint is named after the [`BalancedPool`]
+    /// and the index of the endpoint, e.g. `name[0]`, so the metrics
+    /// exported when enabling the `metrics` feature can be told apart.
+    ///
+    /// Default: `balanced`
+    pub fn name(mut self, value: impl Into<String>) -> Self {
+        self.name = Some(value.into());
+        self
+    }
+
+    /// Sets the [`BalancedPoolConfig`].
+    pub fn config(mut self, value: BalancedPoolConfig) -> Self {
+        self.config = value;
+        self
+    }
+
+    /// Sets the [`BalancedPoolConfig::strategy`].
+    pub fn strategy(mut self, value: BalanceStrategy) -> Self {
+        self.config.strategy = value;
+        self
+    }
+
+    /// Sets the [`BalancedPoolConfig::pool`] used for every endpoint.
+    pub fn pool_config(mut self, value: PoolConfig) -> Self {
+        self.config.pool = value;
+        self
+    }
+
+    /// Sets the [`Runtime`] of the endpoints.
+    ///
+    /// # Important
+    ///
+    /// The [`Runtime`] is optional. Most [`BalancedPool`]s don't need a
+    /// [`Runtime`]. If want to utilize timeouts or background tasks, however,
+    /// a [`Runtime`] must be specified as you will otherwise get a
+    /// [`BuildError::NoRuntimeSpecified`] when trying to build the
+    /// [`BalancedPool`].
+    pub fn runtime(mut self, value: Runtime) -> Self {
+        self.runtime = Some(value);
+        self
+    }
+}
+
+struct Endpoint<M: Manager> {
+    pool: Pool<M>,
+    weight: u32,
+}
+
+struct BalancedPoolInner<M: Manager> {
+    endpoints: Vec<Endpoint<M>>,
+    name: String,
+    config: BalancedPoolConfig,
+    /// Endpoint to start with for [`BalanceStrategy::RoundRobin`].
+    next: AtomicUsize,
+    /// State of the smooth weighted round-robin of
+    /// [`BalanceStrategy::Weighted`].
+    current_weights: Mutex<Vec<i64>>,
+}
+
+impl<M: Manager> BalancedPoolInner<M> {
+    /// Returns the indices of the endpoints in the order they should be
+    /// tried, healthy ones first.
+    fn candidates(&self) -> Vec<usize> {
+        let len = self.endpoints.len();
+        let healthy = self
+            .endpoints
+            .iter()
+            .map(|endpoint| endpoint.pool.status().circuit != CircuitState::Open)
+            .collect::<Vec<_>>();
+        let start = match self.config.strategy {
+            BalanceStrategy::Weighted => self.next_weighted(&healthy),
+            _ => self.next.fetch_add(1, Ordering::Relaxed) % len,
+        };
+        let mut indices = (0..len).map(|i| (start + i) % len).collect::<Vec<_>>();
+        if self.config.strategy == BalanceStrategy::LeastConnections {
+            let connections = self
+                .endpoints
+                .iter()
+                .map(|endpoint| {
+                    let status = endpoint.pool.status();
+                    status.in_use + status.creating + status.recycling
+                })
+                .collect::<Vec<_>>();
+            // The sort is stable, so ties are broken in round-robin order.
+            indices.sort_by_key(|&i| connections[i]);
+        }
+        indices.sort_by_key(|&i| !healthy[i]);
+        indices
+    }
+
+    /// Chooses the next healthy endpoint using the smooth weighted
+    /// round-robin algorithm. All endpoints are taken into account if none of
+    /// them is healthy.
+    fn next_weighted(&self, healthy: &[bool]) -> usize {
+        let all = !healthy.iter().any(|&healthy| healthy);
+        let mut current_weights = self.current_weights.lock().unwrap();
+        let mut total = 0;
+        let mut best: Option<usize> = None;
+        for (i, endpoint) in self.endpoints.iter().enumerate() {
+            if !all && !healthy[i] {
+                continue;
+            }
+            let weight = i64::from(endpoint.weight);
+            current_weights[i] += weight;
+            total += weight;
+            best = match best {
+                Some(best) if current_weights[best] >= current_weights[i] => Some(best),
+                _ => Some(i),
+            };
+        }
+        let best = best.unwrap_or(0);
+        current_weights[best] -= total;
+        best
+    }
+}
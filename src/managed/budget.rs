@@ -0,0 +1,177 @@
+//! Limit of objects shared by multiple [`Pool`]s.
+//!
+//! [`Pool`]: super::Pool
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
+};
+
+use tokio::sync::{Notify, Semaphore};
+
+/// Limit of objects shared by multiple [`Pool`]s, e.g. pools connecting to
+/// the same proxy which only accepts a limited number of connections.
+///
+/// A [`PoolBudget`] is attached to a [`Pool`] using the
+/// [`PoolBuilder::budget()`] method. Every object created by any of the
+/// [`Pool`]s sharing the [`PoolBudget`] counts towards its
+/// [`PoolBudget::max_size`] until it's detached from its [`Pool`] or dropped.
+///
+/// Once the limit is reached, creating an object detaches the least recently
+/// used idle object of another [`Pool`] sharing the [`PoolBudget`]. If there
+/// is none, it waits until an object is returned to or detached from one of
+/// them. This wait counts towards the [`Timeouts::create`].
+///
+/// This struct can be cloned and transferred across thread boundaries and
+/// uses reference counting for its internal state.
+///
+/// [`Pool`]: super::Pool
+/// [`PoolBuilder::budget()`]: super::PoolBuilder::budget
+/// [`Timeouts::create`]: super::Timeouts::create
+#[derive(Clone)]
+pub struct PoolBudget {
+    inner: Arc<BudgetInner>,
+}
+
+impl fmt::Debug for PoolBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoolBudget")
+            .field("max_size", &self.inner.max_size)
+            .field("size", &self.size())
+            .finish()
+    }
+}
+
+impl PoolBudget {
+    /// Creates a new [`PoolBudget`] allowing up to `max_size` objects.
+    #[must_use]
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            inner: Arc::new(BudgetInner {
+                max_size,
+                semaphore: Semaphore::new(max_size),
+                changed: Notify::new(),
+                members: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Returns the maximum number of objects of all [`Pool`]s sharing this
+    /// [`PoolBudget`].
+    ///
+    /// [`Pool`]: super::Pool
+    #[must_use]
+    pub fn max_size(&self) -> usize {
+        self.inner.max_size
+    }
+
+    /// Returns the number of objects which currently count towards this
+    /// [`PoolBudget`] including the ones which are being created.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.inner.max_size - self.available()
+    }
+
+    pub(super) fn available(&self) -> usize {
+        self.inner.semaphore.available_permits()
+    }
+
+    /// Registers a [`Pool`] whose idle objects may be evicted.
+    ///
+    /// [`Pool`]: super::Pool
+    pub(super) fn register(&self, member: Weak<dyn Member>) {
+        let mut members = self.inner.members.lock().unwrap();
+        members.retain(|member| member.strong_count() > 0);
+        members.push(member);
+    }
+
+    /// Wakes up the tasks waiting for idle objects to become available for
+    /// eviction.
+    pub(super) fn notify(&self) {
+        self.inner.changed.notify_waiters();
+    }
+
+    /// Acquires a permit for creating an object of the given `member`.
+    pub(super) async fn acquire(&self, member: &dyn Member) -> BudgetPermit {
+        loop {
+            // Created before checking the budget so no change is missed.
+            let changed = self.inner.changed.notified();
+            if let Ok(permit) = self.inner.semaphore.try_acquire() {
+                // Given back when the `BudgetPermit` is dropped.
+                permit.forget();
+                return BudgetPermit {
+                    budget: self.inner.clone(),
+                };
+            }
+            if !self.evict(member) {
+                changed.await;
+            }
+        }
+    }
+
+    /// Detaches the least recently used idle object of all members except
+    /// the given one. Returns `false` if there is no such object.
+    fn evict(&self, member: &dyn Member) -> bool {
+        let this = member as *const dyn Member as *const ();
+        let members = self
+            .inner
+            .members
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect::<Vec<_>>();
+        let lru = members
+            .iter()
+            .filter(|member| Arc::as_ptr(member) as *const () != this)
+            .filter_map(|member| member.least_recently_used().map(|age| (age, member)))
+            .max_by_key(|(age, _)| *age);
+        match lru {
+            Some((_, member)) => member.evict_idle(),
+            None => false,
+        }
+    }
+}
+
+/// [`Pool`] sharing a [`PoolBudget`].
+///
+/// [`Pool`]: super::Pool
+pub(super) trait Member: Send + Sync {
+    /// Returns the time since the least recently used idle object has been
+    /// used or `None` if there are no idle objects.
+    fn least_recently_used(&self) -> Option<Duration>;
+
+    /// Detaches the least recently used idle object. Returns `false` if
+    /// there are no idle objects.
+    fn evict_idle(&self) -> bool;
+}
+
+struct BudgetInner {
+    max_size: usize,
+    semaphore: Semaphore,
+    /// Notified whenever the [`Status`] of a member changes or a permit is
+    /// given back.
+    ///
+    /// [`Status`]: super::Status
+    changed: Notify,
+    members: Mutex<Vec<Weak<dyn Member>>>,
+}
+
+/// Object counting towards a [`PoolBudget`] until it's dropped.
+pub(super) struct BudgetPermit {
+    budget: Arc<BudgetInner>,
+}
+
+impl fmt::Debug for BudgetPermit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BudgetPermit").finish_non_exhaustive()
+    }
+}
+
+impl Drop for BudgetPermit {
+    fn drop(&mut self) {
+        self.budget.semaphore.add_permits(1);
+        self.budget.changed.notify_waiters();
+    }
+}
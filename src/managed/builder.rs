@@ -4,15 +4,26 @@ use crate::Runtime;
 
 use super::{
     hooks::{Hook, Hooks},
-    Manager, Object, Pool, PoolConfig, QueueMode, Timeouts,
+    observer::Observers,
+    CircuitBreakerConfig, CreateRetryConfig, Manager, Object, ObjectSelector, Pool, PoolBudget,
+    PoolConfig, PoolObserver, QueueMode, RecycleMode, Timeouts, WarmUpReport,
 };
 
 /// Possible errors returned when [`PoolBuilder::build()`] fails to build a
 /// [`Pool`].
 #[derive(Copy, Clone, Debug)]
 pub enum BuildError {
-    /// [`Runtime`] is required du to configured timeouts.
+    /// [`Runtime`] is required due to configured timeouts or background
+    /// tasks.
     NoRuntimeSpecified,
+
+    /// [`BalancedPool`] has no endpoints.
+    ///
+    /// [`BalancedPool`]: super::BalancedPool
+    NoEndpoints,
+
+    /// The [`PoolConfig`] is invalid, e.g. an interval is zero.
+    InvalidConfig(&'static str),
 }
 
 impl fmt::Display for BuildError {
@@ -20,7 +31,16 @@ impl fmt::Display for BuildError {
         match self {
             Self::NoRuntimeSpecified => write!(
                 f,
-                "Error occurred while building the pool: Timeouts require a runtime",
+                "Error occurred while building the pool: Timeouts and background tasks require a runtime",
+            ),
+            Self::NoEndpoints => write!(
+                f,
+                "Error occurred while building the pool: At least one endpoint is required",
+            ),
+            Self::InvalidConfig(msg) => write!(
+                f,
+                "Error occurred while building the pool: Invalid config: {}",
+                msg,
             ),
         }
     }
@@ -29,11 +49,59 @@ impl fmt::Display for BuildError {
 impl std::error::Error for BuildError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::NoRuntimeSpecified => None,
+            Self::NoRuntimeSpecified | Self::NoEndpoints | Self::InvalidConfig(_) => None,
         }
     }
 }
 
+/// Returns an error if a timeout, a background task or retries are
+/// configured without runtime or if the [`PoolConfig`] is invalid.
+pub(super) fn check_config(
+    config: &PoolConfig,
+    runtime: Option<Runtime>,
+) -> Result<(), BuildError> {
+    // Background tasks run in these intervals, so they must not be zero.
+    if config.max_lifetime == Some(Duration::ZERO) {
+        return Err(BuildError::InvalidConfig("`max_lifetime` must not be zero"));
+    }
+    if config.idle_timeout == Some(Duration::ZERO) {
+        return Err(BuildError::InvalidConfig("`idle_timeout` must not be zero"));
+    }
+    if config.leak_detection_threshold == Some(Duration::ZERO) {
+        return Err(BuildError::InvalidConfig(
+            "`leak_detection_threshold` must not be zero",
+        ));
+    }
+    if config.recycle_mode
+        == (RecycleMode::Background {
+            interval: Duration::ZERO,
+        })
+    {
+        return Err(BuildError::InvalidConfig(
+            "`RecycleMode::Background` interval must not be zero",
+        ));
+    }
+    let t = &config.timeouts;
+    if (t.wait.is_some() || t.create.is_some() || t.recycle.is_some()) && runtime.is_none() {
+        return Err(BuildError::NoRuntimeSpecified);
+    }
+    let retry = match config.create_retry {
+        Some(retry) => retry.max_attempts > 1,
+        None => false,
+    };
+    if (config.min_idle > 0
+        || config.max_lifetime.is_some()
+        || config.idle_timeout.is_some()
+        || config.leak_detection_threshold.is_some()
+        || config.recycle_mode != RecycleMode::OnCheckout
+        || retry)
+        && runtime.is_none()
+    {
+        return Err(BuildError::NoRuntimeSpecified);
+    }
+    Ok(())
+}
+
 /// Builder for [`Pool`]s.
 ///
 /// Instances of this are created by calling the [`Pool::builder()`] method.
@@ -47,6 +115,10 @@ where
     pub(crate) config: PoolConfig,
     pub(crate) runtime: Option<Runtime>,
     pub(crate) hooks: Hooks<M>,
+    pub(crate) observers: Observers<M>,
+    pub(crate) name: Option<String>,
+    pub(crate) budget: Option<PoolBudget>,
+    pub(crate) object_selector: Option<Box<dyn ObjectSelector>>,
     _wrapper: PhantomData<fn() -> W>,
 }
 
@@ -62,6 +134,10 @@ where
             .field("config", &self.config)
             .field("runtime", &self.runtime)
             .field("hooks", &self.hooks)
+            .field("observers", &self.observers)
+            .field("name", &self.name)
+            .field("budget", &self.budget)
+            .field("object_selector", &self.object_selector.is_some())
             .field("_wrapper", &self._wrapper)
             .finish()
     }
@@ -78,25 +154,59 @@ where
             config: PoolConfig::default(),
             runtime: None,
             hooks: Hooks::default(),
+            observers: Observers::default(),
+            name: None,
+            budget: None,
+            object_selector: None,
             _wrapper: PhantomData::default(),
         }
     }
 
     /// Builds the [`Pool`].
     ///
+    /// The [`Manager`] is required to be `'static` as background tasks
+    /// holding on to the [`Pool`] are spawned on the [`Runtime`].
+    ///
     /// # Errors
     ///
     /// See [`BuildError`] for details.
-    pub fn build(self) -> Result<Pool<M, W>, BuildError> {
-        // Return an error if a timeout is configured without runtime.
-        let t = &self.config.timeouts;
-        if (t.wait.is_some() || t.create.is_some() || t.recycle.is_some()) && self.runtime.is_none()
-        {
-            return Err(BuildError::NoRuntimeSpecified);
-        }
+    pub fn build(self) -> Result<Pool<M, W>, BuildError>
+    where
+        M: 'static,
+    {
+        check_config(&self.config, self.runtime)?;
         Ok(Pool::from_builder(self))
     }
 
+    /// Builds the [`Pool`] and pre-creates up to `count` objects using
+    /// [`Pool::warm_up()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`BuildError`] for details. Failing object creations don't cause
+    /// an error but are reported via the returned [`WarmUpReport`].
+    pub async fn build_and_warm(
+        self,
+        count: usize,
+    ) -> Result<(Pool<M, W>, WarmUpReport<M::Error>), BuildError>
+    where
+        M: 'static,
+    {
+        let pool = self.build()?;
+        let report = pool.warm_up(count).await;
+        Ok((pool, report))
+    }
+
+    /// Sets the name of the [`Pool`].
+    ///
+    /// The name is used to tell multiple [`Pool`]s apart, e.g. it is
+    /// attached as `pool` label to all metrics exported when enabling the
+    /// `metrics` feature.
+    pub fn name(mut self, value: impl Into<String>) -> Self {
+        self.name = Some(value.into());
+        self
+    }
+
     /// Sets a [`PoolConfig`] to build the [`Pool`] with.
     pub fn config(mut self, value: PoolConfig) -> Self {
         self.config = value;
@@ -109,6 +219,79 @@ where
         self
     }
 
+    /// Sets the [`PoolConfig::min_idle`].
+    ///
+    /// A value greater than `0` requires a [`Runtime`] to be specified.
+    pub fn min_idle(mut self, value: usize) -> Self {
+        self.config.min_idle = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::max_lifetime`].
+    ///
+    /// Setting a value requires a [`Runtime`] to be specified. The value
+    /// must not be zero.
+    pub fn max_lifetime(mut self, value: Option<Duration>) -> Self {
+        self.config.max_lifetime = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::idle_timeout`].
+    ///
+    /// Setting a value requires a [`Runtime`] to be specified. The value
+    /// must not be zero.
+    pub fn idle_timeout(mut self, value: Option<Duration>) -> Self {
+        self.config.idle_timeout = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::high_priority_reserve`].
+    pub fn high_priority_reserve(mut self, value: usize) -> Self {
+        self.config.high_priority_reserve = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::max_waiting`].
+    pub fn max_waiting(mut self, value: Option<usize>) -> Self {
+        self.config.max_waiting = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::circuit_breaker`].
+    pub fn circuit_breaker(mut self, value: Option<CircuitBreakerConfig>) -> Self {
+        self.config.circuit_breaker = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::create_retry`].
+    ///
+    /// Retrying requires a [`Runtime`] to be specified.
+    pub fn create_retry(mut self, value: Option<CreateRetryConfig>) -> Self {
+        self.config.create_retry = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::leak_detection_threshold`].
+    ///
+    /// Setting a value requires a [`Runtime`] to be specified. The value
+    /// must not be zero.
+    pub fn leak_detection_threshold(mut self, value: Option<Duration>) -> Self {
+        self.config.leak_detection_threshold = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::recycle_interval`].
+    pub fn recycle_interval(mut self, value: Option<Duration>) -> Self {
+        self.config.recycle_interval = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::max_shared_users`].
+    pub fn max_shared_users(mut self, value: usize) -> Self {
+        self.config.max_shared_users = value;
+        self
+    }
+
     /// Sets the [`PoolConfig::timeouts`].
     pub fn timeouts(mut self, value: Timeouts) -> Self {
         self.config.timeouts = value;
@@ -139,6 +322,12 @@ where
         self
     }
 
+    /// Sets the [`PoolConfig::recycle_mode`].
+    pub fn recycle_mode(mut self, value: RecycleMode) -> Self {
+        self.config.recycle_mode = value;
+        self
+    }
+
     /// Attaches a `post_create` hook.
     ///
     /// The given `hook` will be called each time right after a new [`Object`]
@@ -166,6 +355,29 @@ where
         self
     }
 
+    /// Attaches a [`PoolObserver`].
+    ///
+    /// The given `observer` will be notified about events happening inside
+    /// the [`Pool`] such as objects being created, recycled or detached.
+    pub fn observer(mut self, observer: impl PoolObserver<M> + 'static) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// Attaches an [`ObjectSelector`] choosing the idle object which is
+    /// handed out next instead of the [`PoolConfig::queue_mode`].
+    pub fn object_selector(mut self, value: impl ObjectSelector + 'static) -> Self {
+        self.object_selector = Some(Box::new(value));
+        self
+    }
+
+    /// Attaches a [`PoolBudget`] which limits the number of objects of
+    /// this [`Pool`] and all other [`Pool`]s sharing it.
+    pub fn budget(mut self, value: PoolBudget) -> Self {
+        self.budget = Some(value);
+        self
+    }
+
     /// Sets the [`Runtime`].
     ///
     /// # Important
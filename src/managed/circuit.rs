@@ -0,0 +1,113 @@
+//! Circuit breaker around [`Manager::create()`].
+//!
+//! [`Manager::create()`]: super::Manager::create
+
+use std::{sync::Mutex, time::Instant};
+
+use super::{CircuitBreakerConfig, CircuitState};
+
+#[derive(Debug, Default)]
+struct State {
+    /// Number of consecutive failures.
+    failures: usize,
+    /// Time the circuit has been opened at.
+    opened_at: Option<Instant>,
+    /// Whether a probing attempt is in progress.
+    probing: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<State>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    pub(crate) fn state(&self) -> CircuitState {
+        let state = self.state.lock().unwrap();
+        match state.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if state.probing || opened_at.elapsed() >= self.config.cooldown => {
+                CircuitState::HalfOpen
+            }
+            Some(_) => CircuitState::Open,
+        }
+    }
+
+    /// Returns an [`Attempt`] if creating a new object is currently allowed.
+    pub(crate) fn attempt(&self) -> Option<Attempt<'_>> {
+        let mut state = self.state.lock().unwrap();
+        let probe = match state.opened_at {
+            None => false,
+            Some(opened_at) => {
+                if state.probing || opened_at.elapsed() < self.config.cooldown {
+                    return None;
+                }
+                state.probing = true;
+                true
+            }
+        };
+        Some(Attempt {
+            breaker: self,
+            probe,
+        })
+    }
+}
+
+/// Attempt to create a new object which has been let through by the
+/// [`CircuitBreaker`].
+///
+/// Dropping an [`Attempt`] without reporting its result allows another
+/// probing attempt to be made.
+#[derive(Debug)]
+pub(crate) struct Attempt<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+}
+
+impl Attempt<'_> {
+    /// Reports a successful attempt. Returns `true` if this changed the
+    /// state of the [`CircuitBreaker`].
+    pub(crate) fn success(mut self) -> bool {
+        self.probe = false;
+        let mut state = self.breaker.state.lock().unwrap();
+        state.failures = 0;
+        state.probing = false;
+        state.opened_at.take().is_some()
+    }
+
+    /// Reports a failed attempt. Returns `true` if this changed the state of
+    /// the [`CircuitBreaker`].
+    pub(crate) fn failure(mut self) -> bool {
+        let mut state = self.breaker.state.lock().unwrap();
+        state.failures += 1;
+        if self.probe {
+            self.probe = false;
+            state.probing = false;
+            state.opened_at = Some(Instant::now());
+            true
+        } else if state.opened_at.is_none()
+            && state.failures >= self.breaker.config.failure_threshold
+        {
+            state.opened_at = Some(Instant::now());
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Drop for Attempt<'_> {
+    fn drop(&mut self) {
+        if self.probe {
+            self.breaker.state.lock().unwrap().probing = false;
+        }
+    }
+}
@@ -15,6 +15,141 @@ pub struct PoolConfig {
     /// [`Pool`]: super::Pool
     pub max_size: usize,
 
+    /// Minimum number of idle objects the [`Pool`] tries to maintain.
+    ///
+    /// When set to a value greater than `0` a background task is spawned on
+    /// the configured [`Runtime`] which creates new objects whenever the
+    /// number of idle objects drops below this value. The [`Pool`] never
+    /// grows beyond its [`PoolConfig::max_size`] in order to do so.
+    ///
+    /// Default: `0`
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_idle: usize,
+
+    /// Maximum lifetime of objects in the [`Pool`].
+    ///
+    /// Objects older than this are removed from the [`Pool`] by a background
+    /// task spawned on the configured [`Runtime`] and are never handed out
+    /// again.
+    ///
+    /// Default: No limit
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_lifetime: Option<Duration>,
+
+    /// Maximum time objects may stay idle in the [`Pool`].
+    ///
+    /// Objects which haven't been used for longer than this (see
+    /// [`Metrics::last_used()`]) are removed from the [`Pool`] by a
+    /// background task spawned on the configured [`Runtime`] and are never
+    /// handed out again.
+    ///
+    /// Default: No limit
+    ///
+    /// [`Metrics::last_used()`]: super::Metrics::last_used
+    /// [`Pool`]: super::Pool
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub idle_timeout: Option<Duration>,
+
+    /// Number of slots of the [`Pool`] reserved for tasks retrieving objects
+    /// with [`Priority::High`].
+    ///
+    /// Tasks with a lower [`Priority`] only get a slot while more than this
+    /// number of slots are available. This prevents them from starving tasks
+    /// with a high priority. The value should be lower than
+    /// [`PoolConfig::max_size`] as tasks with a lower [`Priority`] never get
+    /// a slot otherwise.
+    ///
+    /// Default: `0`
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`Priority`]: super::Priority
+    /// [`Priority::High`]: super::Priority::High
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub high_priority_reserve: usize,
+
+    /// Maximum number of tasks waiting for a slot of the [`Pool`].
+    ///
+    /// Once this number of tasks is waiting, retrieving an object fails
+    /// immediately with a [`PoolError::QueueFull`] instead of waiting. This
+    /// allows shedding load if the backend can't keep up.
+    ///
+    /// Default: No limit
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`PoolError::QueueFull`]: super::PoolError::QueueFull
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_waiting: Option<usize>,
+
+    /// Circuit breaker around the creation of new objects.
+    ///
+    /// Default: No circuit breaker
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+
+    /// Retry policy for creating new objects.
+    ///
+    /// Retrying requires a [`Runtime`] to be specified.
+    ///
+    /// Default: No retries
+    ///
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub create_retry: Option<CreateRetryConfig>,
+
+    /// Time after which a checked out [`Object`] is considered leaked.
+    ///
+    /// Leaked objects are reported once via the
+    /// [`PoolObserver::leak_detected()`] method and logged as warnings when
+    /// enabling the `tracing` feature. The objects currently checked out
+    /// can be listed using [`Pool::checked_out()`].
+    ///
+    /// Leak detection requires a [`Runtime`] to be specified.
+    ///
+    /// Default: No leak detection
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool::checked_out()`]: super::Pool::checked_out
+    /// [`PoolObserver::leak_detected()`]: super::PoolObserver::leak_detected
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub leak_detection_threshold: Option<Duration>,
+
+    /// Objects which have been created or recycled within this interval (see
+    /// [`Metrics::recycled`]) are handed out without calling
+    /// [`Manager::recycle()`] again, so it is called at most once per
+    /// interval for every object. The `pre_recycle` and `post_recycle` hooks
+    /// are still applied.
+    ///
+    /// If this is `None` the [`Manager::recycle_interval()`] is used. Set
+    /// this to [`Duration::ZERO`] for recycling every object.
+    ///
+    /// Default: [`Manager::recycle_interval()`]
+    ///
+    /// [`Manager::recycle()`]: super::Manager::recycle
+    /// [`Manager::recycle_interval()`]: super::Manager::recycle_interval
+    /// [`Metrics::recycled`]: super::Metrics::recycled
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub recycle_interval: Option<Duration>,
+
+    /// Maximum number of users an object retrieved via [`Pool::get_shared()`]
+    /// is lent to concurrently.
+    ///
+    /// Default: `1`
+    ///
+    /// [`Pool::get_shared()`]: super::Pool::get_shared
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "PoolConfig::default_max_shared_users")
+    )]
+    pub max_shared_users: usize,
+
     /// Timeouts of the [`Pool`].
     ///
     /// Default: No timeouts
@@ -25,13 +160,28 @@ pub struct PoolConfig {
 
     /// Queue mode of the [`Pool`].
     ///
-    /// Determines the order of objects being queued and dequeued.
+    /// Determines the order of objects being queued and dequeued unless an
+    /// [`ObjectSelector`] is attached using
+    /// [`PoolBuilder::object_selector()`].
     ///
     /// Default: `Fifo`
     ///
+    /// [`ObjectSelector`]: super::ObjectSelector
     /// [`Pool`]: super::Pool
+    /// [`PoolBuilder::object_selector()`]: super::PoolBuilder::object_selector
     #[cfg_attr(feature = "serde", serde(default))]
     pub queue_mode: QueueMode,
+
+    /// Recycle mode of the [`Pool`].
+    ///
+    /// Determines when objects are recycled after being returned to the
+    /// [`Pool`].
+    ///
+    /// Default: `OnCheckout`
+    ///
+    /// [`Pool`]: super::Pool
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub recycle_mode: RecycleMode,
 }
 
 impl PoolConfig {
@@ -41,10 +191,25 @@ impl PoolConfig {
     pub fn new(max_size: usize) -> Self {
         Self {
             max_size,
+            min_idle: 0,
+            max_lifetime: None,
+            idle_timeout: None,
+            high_priority_reserve: 0,
+            max_waiting: None,
+            circuit_breaker: None,
+            create_retry: None,
+            leak_detection_threshold: None,
+            recycle_interval: None,
+            max_shared_users: Self::default_max_shared_users(),
             timeouts: Timeouts::default(),
             queue_mode: QueueMode::default(),
+            recycle_mode: RecycleMode::default(),
         }
     }
+
+    fn default_max_shared_users() -> usize {
+        1
+    }
 }
 
 impl Default for PoolConfig {
@@ -55,6 +220,210 @@ impl Default for PoolConfig {
     }
 }
 
+/// [`KeyedPool`] configuration.
+///
+/// [`KeyedPool`]: super::KeyedPool
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct KeyedPoolConfig {
+    /// Maximum number of objects of all keys together.
+    ///
+    /// Default: `cpu_count * 4`
+    pub max_size: usize,
+
+    /// Maximum number of sub-pools.
+    ///
+    /// When a sub-pool for a new key is needed and this limit has been
+    /// reached, the least recently used sub-pool without any checked out
+    /// objects is evicted. Sub-pools which are in use are never evicted, so
+    /// the limit is exceeded temporarily if all of them are in use.
+    ///
+    /// Default: No limit
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_pools: Option<usize>,
+
+    /// Configuration of the sub-pools. Its [`PoolConfig::max_size`] limits
+    /// the number of objects per key.
+    ///
+    /// Default: [`PoolConfig::default()`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pool: PoolConfig,
+}
+
+impl KeyedPoolConfig {
+    /// Creates a new [`KeyedPoolConfig`] without a limit of sub-pools and
+    /// with the provided `max_size`.
+    #[must_use]
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            max_pools: None,
+            pool: PoolConfig::default(),
+        }
+    }
+}
+
+impl Default for KeyedPoolConfig {
+    /// Creates a new [`KeyedPoolConfig`] with the `max_size` being set to
+    /// `cpu_count * 4` ignoring any logical CPUs (Hyper-Threading).
+    fn default() -> Self {
+        Self::new(num_cpus::get_physical() * 4)
+    }
+}
+
+/// [`BalancedPool`] configuration.
+///
+/// [`BalancedPool`]: super::BalancedPool
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct BalancedPoolConfig {
+    /// Strategy for choosing the endpoint to retrieve an object from.
+    ///
+    /// Default: [`BalanceStrategy::RoundRobin`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub strategy: BalanceStrategy,
+
+    /// Configuration of the sub-pool of every endpoint. Its
+    /// [`PoolConfig::circuit_breaker`] decides when an endpoint is
+    /// considered unhealthy.
+    ///
+    /// Default: [`PoolConfig::default()`] with a circuit breaker opening
+    /// after `3` consecutive failures for `10` seconds
+    #[cfg_attr(feature = "serde", serde(default = "BalancedPoolConfig::default_pool"))]
+    pub pool: PoolConfig,
+}
+
+impl BalancedPoolConfig {
+    /// Creates a new [`BalancedPoolConfig`] with the provided `strategy` and
+    /// the default [`BalancedPoolConfig::pool`] configuration.
+    #[must_use]
+    pub fn new(strategy: BalanceStrategy) -> Self {
+        Self {
+            strategy,
+            pool: Self::default_pool(),
+        }
+    }
+
+    fn default_pool() -> PoolConfig {
+        PoolConfig {
+            circuit_breaker: Some(CircuitBreakerConfig::new(3, Duration::from_secs(10))),
+            ..PoolConfig::default()
+        }
+    }
+}
+
+impl Default for BalancedPoolConfig {
+    fn default() -> Self {
+        Self::new(BalanceStrategy::default())
+    }
+}
+
+/// Strategy of a [`BalancedPool`] for choosing the endpoint to retrieve an
+/// object from.
+///
+/// Unhealthy endpoints are only chosen if all endpoints are unhealthy.
+///
+/// [`BalancedPool`]: super::BalancedPool
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum BalanceStrategy {
+    /// Choose the endpoints one after another.
+    RoundRobin,
+    /// Choose the endpoint with the least objects which are in use, being
+    /// created or being recycled.
+    LeastConnections,
+    /// Choose the endpoints one after another proportionally to their
+    /// weights.
+    Weighted,
+}
+
+impl Default for BalanceStrategy {
+    fn default() -> Self {
+        Self::RoundRobin
+    }
+}
+
+/// Configuration of the circuit breaker around [`Manager::create()`].
+///
+/// After [`CircuitBreakerConfig::failure_threshold`] consecutive failures to
+/// create an object the circuit opens and attempts to create new objects
+/// fail immediately with a [`PoolError::CircuitOpen`]. Once the
+/// [`CircuitBreakerConfig::cooldown`] is over a single attempt is let
+/// through. The circuit closes again if it succeeds and stays open for
+/// another cool-down period otherwise.
+///
+/// Idle objects are still handed out while the circuit is open.
+///
+/// [`Manager::create()`]: super::Manager::create
+/// [`PoolError::CircuitOpen`]: super::PoolError::CircuitOpen
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CircuitBreakerConfig {
+    /// Number of consecutive failures to create an object after which the
+    /// circuit opens.
+    pub failure_threshold: usize,
+
+    /// Time the circuit stays open before a new object may be created again.
+    pub cooldown: Duration,
+}
+
+impl CircuitBreakerConfig {
+    /// Creates a new [`CircuitBreakerConfig`] with the provided
+    /// `failure_threshold` and `cooldown`.
+    #[must_use]
+    pub fn new(failure_threshold: usize, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold,
+            cooldown,
+        }
+    }
+}
+
+/// Retry policy for [`Manager::create()`].
+///
+/// Failed attempts to create an object are retried if the
+/// [`Manager::is_retryable()`] method returns `true` for the returned error.
+/// The time between two attempts grows exponentially starting at
+/// [`CreateRetryConfig::base_backoff`] up to
+/// [`CreateRetryConfig::max_backoff`].
+///
+/// All attempts and the time between them are bounded by
+/// [`Timeouts::create`]. No further attempt is made if the next one
+/// wouldn't start before it expires.
+///
+/// [`Manager::create()`]: super::Manager::create
+/// [`Manager::is_retryable()`]: super::Manager::is_retryable
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CreateRetryConfig {
+    /// Maximum number of attempts including the first one.
+    pub max_attempts: u32,
+
+    /// Time to wait after the first failed attempt.
+    pub base_backoff: Duration,
+
+    /// Maximum time to wait between two attempts.
+    pub max_backoff: Duration,
+
+    /// Randomize the time between two attempts. When enabled every backoff
+    /// is chosen randomly between half and the full value.
+    pub jitter: bool,
+}
+
+impl CreateRetryConfig {
+    /// Creates a new [`CreateRetryConfig`] with the provided `max_attempts`,
+    /// `base_backoff` and `max_backoff` and jitter being enabled.
+    #[must_use]
+    pub fn new(max_attempts: u32, base_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            max_attempts,
+            base_backoff,
+            max_backoff,
+            jitter: true,
+        }
+    }
+}
+
 /// Timeouts when getting [`Object`]s from a [`Pool`].
 ///
 /// [`Object`]: super::Object
@@ -105,8 +474,13 @@ impl Default for Timeouts {
 
 /// Mode for dequeuing [`Object`]s from a [`Pool`].
 ///
+/// These are the built-in [`ObjectSelector`]s. Custom ones can be attached
+/// using the [`PoolBuilder::object_selector()`] method.
+///
 /// [`Object`]: super::Object
+/// [`ObjectSelector`]: super::ObjectSelector
 /// [`Pool`]: super::Pool
+/// [`PoolBuilder::object_selector()`]: super::PoolBuilder::object_selector
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum QueueMode {
@@ -114,6 +488,15 @@ pub enum QueueMode {
     Fifo,
     /// Dequeue the object that was most recently added (last in first out).
     Lifo,
+    /// Dequeue the object that has been recycled the least often, spreading
+    /// the load over all objects.
+    LeastRecycled,
+    /// Dequeue the object that was created most recently, so older objects
+    /// stay idle and are replaced sooner when using
+    /// [`PoolConfig::max_lifetime`] or [`PoolConfig::idle_timeout`].
+    Newest,
+    /// Dequeue a random object.
+    Random,
 }
 
 impl Default for QueueMode {
@@ -122,6 +505,52 @@ impl Default for QueueMode {
     }
 }
 
+/// Mode determining when [`Manager::recycle()`] is called for [`Object`]s
+/// returned to a [`Pool`].
+///
+/// All modes but [`RecycleMode::OnCheckout`] require a [`Runtime`] to be
+/// specified.
+///
+/// [`Manager::recycle()`]: super::Manager::recycle
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+/// [`Runtime`]: crate::Runtime
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum RecycleMode {
+    /// Idle objects are recycled right before being handed out, so retrieving
+    /// an object includes recycling it.
+    OnCheckout,
+
+    /// Objects are recycled in a spawned task as soon as they're returned to
+    /// the [`Pool`]. They only become available again once being recycled,
+    /// so retrieving an idle object doesn't need to recycle it. Objects
+    /// returned on a thread where no task can be spawned, e.g. outside of a
+    /// Tokio runtime, are detached instead.
+    ///
+    /// [`Pool`]: super::Pool
+    OnReturn,
+
+    /// Objects are returned to the [`Pool`] without being recycled. A
+    /// background task recycles the idle objects which have been used since
+    /// being recycled every `interval`.
+    ///
+    /// Objects are handed out without being recycled even if they haven't
+    /// been recycled since being used.
+    ///
+    /// [`Pool`]: super::Pool
+    Background {
+        /// Interval in which idle objects are recycled. Must not be zero.
+        interval: Duration,
+    },
+}
+
+impl Default for RecycleMode {
+    fn default() -> Self {
+        Self::OnCheckout
+    }
+}
+
 /// This error is used when building pools via the config `create_pool`
 /// methods.
 #[derive(Debug)]
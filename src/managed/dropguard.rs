@@ -3,6 +3,7 @@
 pub(crate) struct DropGuard<F: Fn()>(pub(crate) F);
 
 impl<F: Fn()> DropGuard<F> {
+    #[allow(dead_code)]
     pub(crate) fn disarm(self) {
         std::mem::forget(self)
     }
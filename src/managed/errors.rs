@@ -84,6 +84,31 @@ pub enum PoolError<E> {
 
     /// A `post_create` hook reported an error.
     PostCreateHook(HookError<E>),
+
+    /// [`PoolConfig::max_waiting`] tasks are already waiting for an object.
+    ///
+    /// [`PoolConfig::max_waiting`]: super::PoolConfig::max_waiting
+    QueueFull,
+
+    /// No new object was created because the circuit breaker is open.
+    ///
+    /// See [`CircuitBreakerConfig`] for details.
+    ///
+    /// [`CircuitBreakerConfig`]: super::CircuitBreakerConfig
+    CircuitOpen,
+
+    /// [`Pool`] has been paused with [`PauseOptions::fail_fast`].
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`PauseOptions::fail_fast`]: super::PauseOptions::fail_fast
+    Paused,
+
+    /// More objects were requested at once than the [`Pool`] can ever hand
+    /// out, see [`Pool::get_many()`].
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`Pool::get_many()`]: super::Pool::get_many
+    TooManyObjects,
 }
 
 impl<E> From<E> for PoolError<E> {
@@ -107,6 +132,10 @@ impl<E: fmt::Display> fmt::Display for PoolError<E> {
             Self::Closed => write!(f, "Pool has been closed"),
             Self::NoRuntimeSpecified => write!(f, "No runtime specified"),
             Self::PostCreateHook(e) => writeln!(f, "`post_create` hook failed: {}", e),
+            Self::QueueFull => write!(f, "Too many tasks waiting for an object"),
+            Self::CircuitOpen => write!(f, "Circuit breaker is open"),
+            Self::Paused => write!(f, "Pool has been paused"),
+            Self::TooManyObjects => write!(f, "Too many objects requested at once"),
         }
     }
 }
@@ -114,7 +143,13 @@ impl<E: fmt::Display> fmt::Display for PoolError<E> {
 impl<E: std::error::Error + 'static> std::error::Error for PoolError<E> {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::Timeout(_) | Self::Closed | Self::NoRuntimeSpecified => None,
+            Self::Timeout(_)
+            | Self::Closed
+            | Self::NoRuntimeSpecified
+            | Self::QueueFull
+            | Self::CircuitOpen
+            | Self::Paused
+            | Self::TooManyObjects => None,
             Self::Backend(e) => Some(e),
             Self::PostCreateHook(e) => Some(e),
         }
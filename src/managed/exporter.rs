@@ -0,0 +1,157 @@
+//! Exporter of [`Pool`] metrics using the [`metrics`] crate.
+//!
+//! The exporter is attached to every [`Pool`] as [`PoolObserver`] when
+//! enabling the `metrics` feature. All metrics carry a `pool` label which
+//! is set to the name configured via [`PoolBuilder::name()`].
+//!
+//! [`Pool`]: super::Pool
+//! [`PoolBuilder::name()`]: super::PoolBuilder::name
+
+use std::time::Duration;
+
+use metrics::{
+    counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram, Unit,
+};
+
+use super::{
+    CircuitState, HookError, HookType, Manager, Metrics, PoolError, PoolObserver, RecycleError,
+    Status, TimeoutType,
+};
+
+/// Value of the `pool` label for [`Pool`]s without a name.
+///
+/// [`Pool`]: super::Pool
+const DEFAULT_NAME: &str = "default";
+
+#[derive(Debug)]
+pub(crate) struct MetricsExporter {
+    name: String,
+}
+
+impl MetricsExporter {
+    pub(crate) fn new(name: Option<&str>) -> Self {
+        describe_gauge!("deadpool_pool_max_size", "Maximum size of the pool");
+        describe_gauge!("deadpool_pool_size", "Current size of the pool");
+        describe_gauge!(
+            "deadpool_pool_available",
+            "Number of available objects in the pool"
+        );
+        describe_gauge!(
+            "deadpool_pool_in_use",
+            "Number of objects currently handed out by the pool"
+        );
+        describe_gauge!(
+            "deadpool_pool_creating",
+            "Number of objects currently being created"
+        );
+        describe_gauge!(
+            "deadpool_pool_recycling",
+            "Number of objects currently being recycled"
+        );
+        describe_gauge!(
+            "deadpool_pool_waiting",
+            "Number of futures waiting for an object"
+        );
+        describe_gauge!(
+            "deadpool_pool_circuit_state",
+            "State of the circuit breaker (0 = closed, 1 = half-open, 2 = open)"
+        );
+        describe_histogram!(
+            "deadpool_checkout_wait_seconds",
+            Unit::Seconds,
+            "Time spent waiting for a slot of the pool"
+        );
+        describe_histogram!(
+            "deadpool_create_duration_seconds",
+            Unit::Seconds,
+            "Time spent creating new objects"
+        );
+        describe_histogram!(
+            "deadpool_recycle_duration_seconds",
+            Unit::Seconds,
+            "Time spent recycling objects"
+        );
+        describe_counter!(
+            "deadpool_create_errors_total",
+            "Number of failed attempts to create an object"
+        );
+        describe_counter!(
+            "deadpool_recycle_errors_total",
+            "Number of failed attempts to recycle an object"
+        );
+        describe_counter!(
+            "deadpool_hook_errors_total",
+            "Number of errors returned by hooks"
+        );
+        describe_counter!(
+            "deadpool_detached_total",
+            "Number of objects detached from the pool"
+        );
+        describe_counter!("deadpool_timeouts_total", "Number of timeouts");
+        Self {
+            name: name.unwrap_or(DEFAULT_NAME).to_owned(),
+        }
+    }
+}
+
+impl<M: Manager> PoolObserver<M> for MetricsExporter {
+    fn checkout_wait(&self, duration: Duration) {
+        histogram!("deadpool_checkout_wait_seconds", duration, "pool" => self.name.clone());
+    }
+
+    fn create_success(&self, duration: Duration) {
+        histogram!("deadpool_create_duration_seconds", duration, "pool" => self.name.clone());
+    }
+
+    fn create_failure(&self, duration: Duration, _error: &PoolError<M::Error>) {
+        histogram!("deadpool_create_duration_seconds", duration, "pool" => self.name.clone());
+        counter!("deadpool_create_errors_total", 1, "pool" => self.name.clone());
+    }
+
+    fn recycle_success(&self, duration: Duration) {
+        histogram!("deadpool_recycle_duration_seconds", duration, "pool" => self.name.clone());
+    }
+
+    fn recycle_failure(&self, duration: Duration, _error: &RecycleError<M::Error>) {
+        histogram!("deadpool_recycle_duration_seconds", duration, "pool" => self.name.clone());
+        counter!("deadpool_recycle_errors_total", 1, "pool" => self.name.clone());
+    }
+
+    fn hook_failure(&self, hook_type: HookType, _error: &HookError<M::Error>) {
+        let hook = match hook_type {
+            HookType::PostCreate => "post_create",
+            HookType::PreRecycle => "pre_recycle",
+            HookType::PostRecycle => "post_recycle",
+        };
+        counter!("deadpool_hook_errors_total", 1, "pool" => self.name.clone(), "hook" => hook);
+    }
+
+    fn detach(&self, _metrics: &Metrics) {
+        counter!("deadpool_detached_total", 1, "pool" => self.name.clone());
+    }
+
+    fn timeout(&self, timeout_type: TimeoutType) {
+        let timeout_type = match timeout_type {
+            TimeoutType::Wait => "wait",
+            TimeoutType::Create => "create",
+            TimeoutType::Recycle => "recycle",
+        };
+        counter!("deadpool_timeouts_total", 1, "pool" => self.name.clone(), "type" => timeout_type);
+    }
+
+    fn status(&self, status: Status) {
+        gauge!("deadpool_pool_max_size", status.max_size as f64, "pool" => self.name.clone());
+        gauge!("deadpool_pool_size", status.size as f64, "pool" => self.name.clone());
+        gauge!("deadpool_pool_available", status.available as f64, "pool" => self.name.clone());
+        gauge!("deadpool_pool_in_use", status.in_use as f64, "pool" => self.name.clone());
+        gauge!("deadpool_pool_creating", status.creating as f64, "pool" => self.name.clone());
+        gauge!("deadpool_pool_recycling", status.recycling as f64, "pool" => self.name.clone());
+        gauge!("deadpool_pool_waiting", status.waiting as f64, "pool" => self.name.clone());
+        let circuit_state = match status.circuit {
+            CircuitState::Closed => 0.0,
+            CircuitState::HalfOpen => 1.0,
+            CircuitState::Open => 2.0,
+        };
+        gauge!("deadpool_pool_circuit_state", circuit_state, "pool" => self.name.clone());
+    }
+}
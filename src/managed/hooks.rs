@@ -60,6 +60,21 @@ impl<M: Manager> fmt::Debug for Hook<M> {
     }
 }
 
+/// Possible hooks which can be attached to a [`Pool`].
+///
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug)]
+pub enum HookType {
+    /// Hook called right after a new object has been created.
+    PostCreate,
+
+    /// Hook called right before an object will be recycled.
+    PreRecycle,
+
+    /// Hook called right after an object has been recycled.
+    PostRecycle,
+}
+
 /// Error which is returned by `pre_create`, `pre_recycle` and
 /// `post_recycle` hooks.
 #[derive(Debug)]
@@ -95,6 +110,7 @@ impl<E: std::error::Error + 'static> std::error::Error for HookError<E> {
 }
 
 pub(crate) struct HookVec<M: Manager> {
+    hook_type: HookType,
     vec: Vec<Hook<M>>,
 }
 
@@ -102,19 +118,28 @@ pub(crate) struct HookVec<M: Manager> {
 impl<M: Manager> fmt::Debug for HookVec<M> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("HookVec")
+            .field("hook_type", &self.hook_type)
             //.field("fns", &self.fns)
             .finish_non_exhaustive()
     }
 }
 
-// Implemented manually to avoid unnecessary trait bound on `M` type parameter.
-impl<M: Manager> Default for HookVec<M> {
-    fn default() -> Self {
-        Self { vec: Vec::new() }
-    }
-}
-
 impl<M: Manager> HookVec<M> {
+    fn new(hook_type: HookType) -> Self {
+        Self {
+            hook_type,
+            vec: Vec::new(),
+        }
+    }
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            target = "deadpool",
+            level = "debug",
+            skip_all,
+            fields(hook_type = ?self.hook_type),
+        )
+    )]
     pub(crate) async fn apply(
         &self,
         inner: &mut ObjectInner<M>,
@@ -156,9 +181,9 @@ impl<M: Manager> fmt::Debug for Hooks<M> {
 impl<M: Manager> Default for Hooks<M> {
     fn default() -> Self {
         Self {
-            pre_recycle: HookVec::default(),
-            post_create: HookVec::default(),
-            post_recycle: HookVec::default(),
+            pre_recycle: HookVec::new(HookType::PreRecycle),
+            post_create: HookVec::new(HookType::PostCreate),
+            post_recycle: HookVec::new(HookType::PostRecycle),
         }
     }
 }
@@ -0,0 +1,57 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Polls all given futures concurrently and resolves to their outputs in
+/// the same order once all of them have completed.
+pub(crate) fn join_all<F: Future>(futures: impl IntoIterator<Item = F>) -> JoinAll<F> {
+    JoinAll {
+        futures: futures
+            .into_iter()
+            .map(|future| MaybeDone::Pending(Box::pin(future)))
+            .collect(),
+    }
+}
+
+enum MaybeDone<F: Future> {
+    Pending(Pin<Box<F>>),
+    Done(Option<F::Output>),
+}
+
+/// Future returned by [`join_all()`].
+pub(crate) struct JoinAll<F: Future> {
+    futures: Vec<MaybeDone<F>>,
+}
+
+// The futures are boxed and the outputs are never pinned.
+impl<F: Future> Unpin for JoinAll<F> {}
+
+impl<F: Future> Future for JoinAll<F> {
+    type Output = Vec<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut all_done = true;
+        for future in self.futures.iter_mut() {
+            if let MaybeDone::Pending(f) = future {
+                match f.as_mut().poll(cx) {
+                    Poll::Ready(output) => *future = MaybeDone::Done(Some(output)),
+                    Poll::Pending => all_done = false,
+                }
+            }
+        }
+        if !all_done {
+            return Poll::Pending;
+        }
+        Poll::Ready(
+            self.futures
+                .iter_mut()
+                .map(|future| match future {
+                    MaybeDone::Done(output) => output.take().unwrap(),
+                    MaybeDone::Pending(_) => unreachable!(),
+                })
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,298 @@
+//! Pool of [`Pool`]s indexed by a key.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    hash::Hash,
+    panic::Location,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use deadpool_runtime::Runtime;
+
+use super::{
+    builder::check_config, BuildError, KeyedPoolConfig, Manager, Object, Pool, PoolBudget,
+    PoolBuilder, PoolConfig, PoolError, Priority, Status,
+};
+
+type Factory<K, M> = Box<dyn Fn(&K) -> M + Send + Sync>;
+
+/// Name of [`KeyedPool`]s which haven't been named using
+/// [`KeyedPoolBuilder::name()`].
+const DEFAULT_NAME: &str = "keyed";
+
+/// Pool of [`Pool`]s indexed by a key with a global limit of objects.
+///
+/// The sub-pool of a key is created lazily the first time an object of that
+/// key is requested using the [`Manager`] returned by the factory passed to
+/// [`KeyedPool::builder()`]. Every sub-pool is limited by the
+/// [`KeyedPoolConfig::pool`] configuration while all sub-pools together
+/// never manage more than [`KeyedPoolConfig::max_size`] objects.
+///
+/// The sub-pools share a [`PoolBudget`], so once that limit is reached,
+/// creating an object for one key detaches the least recently used idle
+/// object of another key. If there is none, it waits until an object of
+/// another key is returned or detached.
+///
+/// This struct can be cloned and transferred across thread boundaries and
+/// uses reference counting for its internal state.
+pub struct KeyedPool<K, M: Manager> {
+    inner: Arc<KeyedPoolInner<K, M>>,
+}
+
+// Implemented manually to avoid unnecessary trait bound on `M` type parameter.
+impl<K, M: Manager> fmt::Debug for KeyedPool<K, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyedPool")
+            .field("name", &self.inner.name)
+            .field("config", &self.inner.config)
+            .field("runtime", &self.inner.runtime)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K, M: Manager> Clone for KeyedPool<K, M> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K, M> KeyedPool<K, M>
+where
+    K: Clone + Eq + Hash + fmt::Debug,
+    M: Manager + 'static,
+{
+    /// Instantiates a builder for a new [`KeyedPool`] which creates the
+    /// [`Manager`] of every sub-pool by calling the given `factory` with the
+    /// key of the sub-pool.
+    pub fn builder(factory: impl Fn(&K) -> M + Send + Sync + 'static) -> KeyedPoolBuilder<K, M> {
+        KeyedPoolBuilder {
+            factory: Box::new(factory),
+            name: None,
+            config: KeyedPoolConfig::default(),
+            runtime: None,
+        }
+    }
+
+    /// Retrieves an [`Object`] of the given `key` from this [`KeyedPool`] or
+    /// waits for one to become available.
+    ///
+    /// The sub-pool of the `key` is created if it doesn't exist yet.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    #[track_caller]
+    pub fn get(&self, key: &K) -> impl Future<Output = Result<Object<M>, PoolError<M::Error>>> {
+        let pool = self.inner.pool(key);
+        let location = Location::caller();
+        async move {
+            pool.checkout(pool.timeouts(), Priority::Normal, location)
+                .await
+        }
+    }
+
+    /// Retrieves the [`Status`] of the sub-pool of the given `key` or `None`
+    /// if there is no such sub-pool.
+    #[must_use]
+    pub fn status(&self, key: &K) -> Option<Status> {
+        let pools = self.inner.pools.lock().unwrap();
+        pools.get(key).map(|entry| entry.pool.status())
+    }
+
+    /// Retrieves the [`Status`] of all sub-pools.
+    #[must_use]
+    pub fn statuses(&self) -> HashMap<K, Status> {
+        let pools = self.inner.pools.lock().unwrap();
+        pools
+            .iter()
+            .map(|(key, entry)| (key.clone(), entry.pool.status()))
+            .collect()
+    }
+
+    /// Returns the number of objects of all keys together.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.inner.budget.size()
+    }
+
+    /// Returns the name of this [`KeyedPool`], see
+    /// [`KeyedPoolBuilder::name()`].
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.inner.name
+    }
+
+    /// Returns the [`KeyedPoolConfig`] of this [`KeyedPool`].
+    #[must_use]
+    pub fn config(&self) -> &KeyedPoolConfig {
+        &self.inner.config
+    }
+}
+
+/// Builder for [`KeyedPool`]s.
+///
+/// Instances of this are created by calling the [`KeyedPool::builder()`]
+/// method.
+#[must_use = "builder does nothing itself, use `.build()` to build it"]
+pub struct KeyedPoolBuilder<K, M> {
+    factory: Factory<K, M>,
+    name: Option<String>,
+    config: KeyedPoolConfig,
+    runtime: Option<Runtime>,
+}
+
+// Implemented manually as the factory doesn't implement `Debug`.
+impl<K, M> fmt::Debug for KeyedPoolBuilder<K, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyedPoolBuilder")
+            .field("name", &self.name)
+            .field("config", &self.config)
+            .field("runtime", &self.runtime)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K, M> KeyedPoolBuilder<K, M>
+where
+    K: Clone + Eq + Hash + fmt::Debug,
+    M: Manager + 'static,
+{
+    /// Builds the [`KeyedPool`].
+    ///
+    /// # Errors
+    ///
+    /// See [`BuildError`] for details.
+    pub fn build(self) -> Result<KeyedPool<K, M>, BuildError> {
+        check_config(&self.config.pool, self.runtime)?;
+        Ok(KeyedPool {
+            inner: Arc::new(KeyedPoolInner {
+                factory: self.factory,
+                name: self.name.unwrap_or_else(|| DEFAULT_NAME.to_owned()),
+                config: self.config,
+                runtime: self.runtime,
+                pools: Mutex::new(HashMap::new()),
+                budget: PoolBudget::new(self.config.max_size),
+            }),
+        })
+    }
+
+    /// Sets the name of the [`KeyedPool`].
+    ///
+    /// Every sub-pool is named after the [`KeyedPool`] and the [`fmt::Debug`]
+    /// representation of its key, e.g. `name["key"]`, so the metrics
+    /// exported when enabling the `metrics` feature can be told apart.
+    ///
+    /// Default: `keyed`
+    pub fn name(mut self, value: impl Into<String>) -> Self {
+        self.name = Some(value.into());
+        self
+    }
+
+    /// Sets the [`KeyedPoolConfig`].
+    pub fn config(mut self, value: KeyedPoolConfig) -> Self {
+        self.config = value;
+        self
+    }
+
+    /// Sets the [`KeyedPoolConfig::max_size`].
+    pub fn max_size(mut self, value: usize) -> Self {
+        self.config.max_size = value;
+        self
+    }
+
+    /// Sets the [`KeyedPoolConfig::max_pools`].
+    pub fn max_pools(mut self, value: Option<usize>) -> Self {
+        self.config.max_pools = value;
+        self
+    }
+
+    /// Sets the [`KeyedPoolConfig::pool`] used for every sub-pool.
+    pub fn pool_config(mut self, value: PoolConfig) -> Self {
+        self.config.pool = value;
+        self
+    }
+
+    /// Sets the [`Runtime`] of the sub-pools.
+    ///
+    /// # Important
+    ///
+    /// The [`Runtime`] is optional. Most [`KeyedPool`]s don't need a
+    /// [`Runtime`]. If want to utilize timeouts or background tasks, however,
+    /// a [`Runtime`] must be specified as you will otherwise get a
+    /// [`BuildError::NoRuntimeSpecified`] when trying to build the
+    /// [`KeyedPool`].
+    pub fn runtime(mut self, value: Runtime) -> Self {
+        self.runtime = Some(value);
+        self
+    }
+}
+
+struct Entry<M: Manager> {
+    pool: Pool<M>,
+    last_used: Instant,
+}
+
+struct KeyedPoolInner<K, M: Manager> {
+    factory: Factory<K, M>,
+    name: String,
+    config: KeyedPoolConfig,
+    runtime: Option<Runtime>,
+    pools: Mutex<HashMap<K, Entry<M>>>,
+    budget: PoolBudget,
+}
+
+impl<K, M> KeyedPoolInner<K, M>
+where
+    K: Clone + Eq + Hash + fmt::Debug,
+    M: Manager + 'static,
+{
+    /// Returns the sub-pool of the given `key` and creates it if it doesn't
+    /// exist yet.
+    fn pool(&self, key: &K) -> Pool<M> {
+        let mut pools = self.pools.lock().unwrap();
+        if let Some(entry) = pools.get_mut(key) {
+            entry.last_used = Instant::now();
+            return entry.pool.clone();
+        }
+        if let Some(max_pools) = self.config.max_pools {
+            if pools.len() >= max_pools {
+                Self::evict_pool(&mut pools);
+            }
+        }
+        let mut builder = PoolBuilder::new((self.factory)(key));
+        builder.name = Some(format!("{}[{:?}]", self.name, key));
+        builder.config = self.config.pool;
+        builder.runtime = self.runtime;
+        builder.budget = Some(self.budget.clone());
+        let pool = Pool::from_builder(builder);
+        let _ = pools.insert(
+            key.clone(),
+            Entry {
+                pool: pool.clone(),
+                last_used: Instant::now(),
+            },
+        );
+        pool
+    }
+
+    /// Evicts the least recently used sub-pool which isn't in use.
+    fn evict_pool(pools: &mut HashMap<K, Entry<M>>) {
+        let key = pools
+            .iter()
+            // Nobody else holds the sub-pool while the lock is held, so no
+            // objects can be checked out of it anymore.
+            .filter(|(_, entry)| {
+                Arc::strong_count(&entry.pool.inner) == 1 && entry.pool.status().in_use == 0
+            })
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| key.clone());
+        if let Some(entry) = key.and_then(|key| pools.remove(&key)) {
+            entry.pool.retain(|_, _| false);
+        }
+    }
+}
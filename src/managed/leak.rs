@@ -0,0 +1,174 @@
+//! Detection of [`Object`]s which are checked out for longer than the
+//! [`PoolConfig::leak_detection_threshold`].
+//!
+//! [`Object`]: super::Object
+//! [`PoolConfig::leak_detection_threshold`]: super::PoolConfig::leak_detection_threshold
+
+use std::{
+    collections::HashMap,
+    panic::Location,
+    sync::{Mutex, Weak},
+    time::{Duration, Instant},
+};
+
+use deadpool_runtime::Runtime;
+
+use super::{Manager, Metrics, PoolInner};
+
+/// Information about an [`Object`] which is currently checked out of a
+/// [`Pool`].
+///
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug)]
+pub struct CheckoutInfo {
+    /// Location of the code which checked out the [`Object`], i.e. the
+    /// caller of [`Pool::get()`] or one of its variants.
+    ///
+    /// [`Object`]: super::Object
+    /// [`Pool::get()`]: super::Pool::get
+    pub location: &'static Location<'static>,
+
+    /// The time when the [`Object`] was checked out.
+    ///
+    /// [`Object`]: super::Object
+    pub checked_out: Instant,
+
+    /// The [`Metrics`] of the [`Object`] at the time it was checked out.
+    ///
+    /// [`Object`]: super::Object
+    pub metrics: Metrics,
+}
+
+impl CheckoutInfo {
+    /// Returns the time since the [`Object`] was checked out.
+    ///
+    /// [`Object`]: super::Object
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.checked_out.elapsed()
+    }
+}
+
+#[derive(Debug)]
+struct Checkout {
+    info: CheckoutInfo,
+    /// Indicates whether this checkout has already been reported as leaked.
+    reported: bool,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    next_id: u64,
+    checkouts: HashMap<u64, Checkout>,
+}
+
+/// Registry of the [`Object`]s which are currently checked out.
+///
+/// [`Object`]: super::Object
+#[derive(Debug)]
+pub(super) struct LeakDetector {
+    threshold: Duration,
+    state: Mutex<State>,
+}
+
+impl LeakDetector {
+    pub(super) fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Registers a checkout and returns its id which must be passed to
+    /// [`LeakDetector::checkin()`] once the object is returned.
+    pub(super) fn checkout(&self, info: CheckoutInfo) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        let id = state.next_id;
+        state.next_id += 1;
+        let _ = state.checkouts.insert(
+            id,
+            Checkout {
+                info,
+                reported: false,
+            },
+        );
+        id
+    }
+
+    pub(super) fn checkin(&self, id: u64) {
+        let _ = self.state.lock().unwrap().checkouts.remove(&id);
+    }
+
+    /// Returns all checkouts, the oldest one first.
+    pub(super) fn checked_out(&self) -> Vec<CheckoutInfo> {
+        let state = self.state.lock().unwrap();
+        let mut vec = state
+            .checkouts
+            .values()
+            .map(|checkout| checkout.info)
+            .collect::<Vec<_>>();
+        vec.sort_by_key(|info| info.checked_out);
+        vec
+    }
+
+    /// Returns the checkouts which exceeded the threshold and haven't been
+    /// reported before, the oldest one first.
+    fn detect(&self) -> Vec<CheckoutInfo> {
+        let mut state = self.state.lock().unwrap();
+        let mut vec = Vec::new();
+        for checkout in state.checkouts.values_mut() {
+            if !checkout.reported && checkout.info.duration() > self.threshold {
+                checkout.reported = true;
+                vec.push(checkout.info);
+            }
+        }
+        vec.sort_by_key(|info| info.checked_out);
+        vec
+    }
+}
+
+/// Spawns the task reporting leaked objects of the given [`Pool`] to its
+/// [`PoolObserver`]s.
+///
+/// The task checks for leaked objects twice per `threshold`. It only holds a
+/// [`Weak`] reference to the [`Pool`] while sleeping and stops as soon as the
+/// [`Pool`] is dropped or closed.
+///
+/// [`Pool`]: super::Pool
+/// [`PoolObserver`]: super::PoolObserver
+pub(super) fn spawn<M: Manager + 'static>(
+    runtime: Runtime,
+    pool: Weak<PoolInner<M>>,
+    threshold: Duration,
+) {
+    let interval = threshold / 2;
+    runtime.spawn(async move {
+        loop {
+            runtime.sleep(interval).await;
+            let inner = match pool.upgrade() {
+                Some(inner) => inner,
+                None => break,
+            };
+            if inner.semaphore.is_closed() {
+                break;
+            }
+            let leak_detector = match &inner.leak_detector {
+                Some(leak_detector) => leak_detector,
+                None => break,
+            };
+            for info in leak_detector.detect() {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    target: "deadpool",
+                    pool = inner.name.as_deref().unwrap_or_default(),
+                    location = %info.location,
+                    duration = ?info.duration(),
+                    metrics = ?info.metrics,
+                    "Object has been checked out for longer than the leak detection threshold",
+                );
+                inner.observers.leak_detected(&info);
+            }
+        }
+    });
+}
@@ -6,8 +6,10 @@ use std::time::{Duration, Instant};
 pub struct Metrics {
     /// The instant when this object was created
     pub created: Instant,
-    /// The instant when this object was last used
+    /// The instant when this object was last recycled
     pub recycled: Option<Instant>,
+    /// The instant when this object was last returned to the pool
+    pub returned: Option<Instant>,
     /// The number of times the objects was recycled
     pub recycle_count: usize,
 }
@@ -17,9 +19,14 @@ impl Metrics {
     pub fn age(&self) -> Duration {
         self.created.elapsed()
     }
-    /// Get the time elapsed when this object was last used
+    /// Get the time elapsed when this object was last used, i.e. created,
+    /// recycled or returned to the pool
     pub fn last_used(&self) -> Duration {
-        self.recycled.unwrap_or(self.created).elapsed()
+        let last_used = match (self.recycled, self.returned) {
+            (Some(recycled), Some(returned)) => recycled.max(returned),
+            (recycled, returned) => recycled.or(returned).unwrap_or(self.created),
+        };
+        last_used.elapsed()
     }
 }
 
@@ -28,6 +35,7 @@ impl Default for Metrics {
         Self {
             created: Instant::now(),
             recycled: None,
+            returned: None,
             recycle_count: 0,
         }
     }
@@ -50,13 +50,33 @@
 //! For a more complete example please see
 //! [`deadpool-postgres`](https://crates.io/crates/deadpool-postgres) crate.
 
+mod balanced;
+mod budget;
 mod builder;
+mod circuit;
 mod config;
 mod dropguard;
 mod errors;
+#[cfg(feature = "metrics")]
+mod exporter;
 mod hooks;
+mod join;
+mod keyed;
+mod leak;
 mod metrics;
+mod observer;
+mod pause;
+mod queue;
+mod reaper;
+mod recycler;
 pub mod reexports;
+mod replenish;
+mod retry;
+mod select;
+mod shared;
+#[cfg(feature = "tracing")]
+mod trace;
+mod warm_up;
 
 #[deprecated(
     since = "0.9.1",
@@ -70,6 +90,7 @@ use std::{
     future::Future,
     marker::PhantomData,
     ops::{Deref, DerefMut},
+    panic::Location,
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc, Mutex, Weak,
@@ -83,17 +104,31 @@ use deadpool_runtime::Runtime;
 // deadpool has a MSRV of 1.54
 #[allow(deprecated)]
 use retain_mut::RetainMut;
-use tokio::sync::{Semaphore, TryAcquireError};
+use tokio::sync::{Notify, Semaphore, SemaphorePermit, TryAcquireError};
 
-pub use crate::Status;
+use crate::stats::StatsCounters;
+pub use crate::{CircuitState, Stats, Status};
 
 use self::dropguard::DropGuard;
 pub use self::{
+    balanced::{BalancedPool, BalancedPoolBuilder},
+    budget::PoolBudget,
     builder::{BuildError, PoolBuilder},
-    config::{CreatePoolError, PoolConfig, QueueMode, Timeouts},
+    config::{
+        BalanceStrategy, BalancedPoolConfig, CircuitBreakerConfig, CreatePoolError,
+        CreateRetryConfig, KeyedPoolConfig, PoolConfig, QueueMode, RecycleMode, Timeouts,
+    },
     errors::{PoolError, RecycleError, TimeoutType},
-    hooks::{Hook, HookError, HookFuture, HookResult},
+    hooks::{Hook, HookError, HookFuture, HookResult, HookType},
+    keyed::{KeyedPool, KeyedPoolBuilder},
+    leak::CheckoutInfo,
     metrics::Metrics,
+    observer::PoolObserver,
+    pause::PauseOptions,
+    queue::Priority,
+    select::{IdleObjects, ObjectSelector},
+    shared::SharedObject,
+    warm_up::WarmUpReport,
 };
 
 /// Result type of the [`Manager::recycle()`] method.
@@ -103,7 +138,7 @@ pub type RecycleResult<E> = Result<(), RecycleError<E>>;
 #[async_trait]
 pub trait Manager: Sync + Send {
     /// Type of [`Object`]s that this [`Manager`] creates and recycles.
-    type Type;
+    type Type: Send;
     /// Error that this [`Manager`] can return when creating and/or recycling
     /// [`Object`]s.
     type Error;
@@ -125,6 +160,36 @@ pub trait Manager: Sync + Send {
     /// any references to the handed out [`Object`]s then the default
     /// implementation can be used which does nothing.
     fn detach(&self, _obj: &mut Self::Type) {}
+
+    /// Destroys an instance of [`Manager::Type`] which has been detached
+    /// from the [`Pool`] using [`Manager::detach()`].
+    ///
+    /// This method is called by [`Pool::close_gracefully()`] and allows to
+    /// shut down objects politely, e.g. by telling the server that the
+    /// connection is about to be closed. The default implementation simply
+    /// drops the object.
+    async fn destroy(&self, _obj: Self::Type) {}
+
+    /// Indicates whether creating a new instance of [`Manager::Type`] should
+    /// be retried after [`Manager::create()`] returned the given `error`.
+    ///
+    /// This method is only called if a [`PoolConfig::create_retry`] policy
+    /// is configured. The default implementation considers all errors to be
+    /// retryable.
+    fn is_retryable(&self, _error: &Self::Error) -> bool {
+        true
+    }
+
+    /// Returns the [`PoolConfig::recycle_interval`] to use if none is
+    /// configured.
+    ///
+    /// Backends whose [`Manager::recycle()`] only verifies the health of the
+    /// object can use this to skip it for objects which have been recycled
+    /// recently. The default implementation returns `None` so every object
+    /// is recycled.
+    fn recycle_interval(&self) -> Option<Duration> {
+        None
+    }
 }
 
 /// Wrapper around the actual pooled object which implements [`Deref`],
@@ -139,6 +204,13 @@ pub struct Object<M: Manager> {
 
     /// Pool to return the pooled object to.
     pool: Weak<PoolInner<M>>,
+
+    /// Id of the checkout registered with the [`PoolInner::leak_detector`].
+    checkout_id: Option<u64>,
+
+    /// Indicates whether the object is detached instead of being returned to
+    /// the [`Pool`] when being dropped.
+    broken: bool,
 }
 
 impl<M> fmt::Debug for Object<M>
@@ -149,29 +221,117 @@ where
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Object")
             .field("inner", &self.inner)
+            .field("broken", &self.broken)
             .finish()
     }
 }
 
+/// Stage of an [`UnreadyObject`] determining the counter of the [`Slots`] it
+/// is accounted for.
+#[derive(Clone, Copy, Debug)]
+enum Stage {
+    Creating,
+    Recycling,
+    /// Recycled after being returned, see [`RecycleMode::OnReturn`]. Unlike
+    /// the other stages no semaphore permit is held for the object, so one
+    /// is added when the object leaves this stage.
+    Returning,
+}
+
+/// Object which is being created or recycled.
+///
+/// The object is detached from the [`Pool`] when being dropped unless it has
+/// been handed out or added to the idle objects.
 struct UnreadyObject<'a, M: Manager> {
+    /// The object. This is `None` while [`Manager::create()`] is running.
     inner: Option<ObjectInner<M>>,
+    /// `None` once the object has left the [`Stage`].
+    stage: Option<Stage>,
+    /// Generation of the [`Pool`] when the object started being created.
+    generation: u64,
     pool: &'a PoolInner<M>,
 }
 
 impl<'a, M: Manager> UnreadyObject<'a, M> {
-    fn ready(mut self) -> ObjectInner<M> {
-        self.inner.take().unwrap()
+    /// Reserves a slot for an object which is about to be created.
+    fn create(pool: &'a PoolInner<M>) -> Self {
+        let mut slots = pool.slots.lock().unwrap();
+        slots.size += 1;
+        slots.creating += 1;
+        let generation = slots.generation;
+        drop(slots);
+        pool.status_changed();
+        Self {
+            inner: None,
+            stage: Some(Stage::Creating),
+            generation,
+            pool,
+        }
     }
     fn inner(&mut self) -> &mut ObjectInner<M> {
         return self.inner.as_mut().unwrap();
     }
+    /// Marks the object as being handed out to a user.
+    ///
+    /// Returns [`None`] and detaches the object if it was invalidated while
+    /// being created or recycled.
+    fn into_in_use(mut self) -> Option<ObjectInner<M>> {
+        let mut slots = self.pool.slots.lock().unwrap();
+        if self.inner().generation != slots.generation {
+            drop(slots);
+            return None;
+        }
+        let stage = self.stage.take().unwrap();
+        slots.leave(stage);
+        slots.in_use += 1;
+        drop(slots);
+        self.pool.status_changed();
+        self.inner.take()
+    }
+    /// Adds the object to the idle objects without returning a semaphore
+    /// permit.
+    fn into_idle(mut self) {
+        let stage = self.stage.take().unwrap();
+        let mut inner = self.inner.take().unwrap();
+        let mut slots = self.pool.slots.lock().unwrap();
+        slots.leave(stage);
+        let add_permit = slots.add_permit(stage);
+        let stale = inner.generation != slots.generation;
+        if !stale && (slots.size <= slots.max_size || slots.draining) {
+            slots.vec.push_back(inner);
+            drop(slots);
+        } else {
+            slots.size -= 1;
+            drop(slots);
+            self.pool.manager.detach(&mut inner.obj);
+            self.pool.observers.detach(&inner.metrics);
+            self.pool.stats.detached();
+        }
+        if add_permit {
+            self.pool.semaphore.add_permits(1);
+        }
+        self.pool.status_changed();
+    }
 }
 
 impl<'a, M: Manager> Drop for UnreadyObject<'a, M> {
     fn drop(&mut self) {
-        if let Some(mut inner) = self.inner.take() {
-            self.pool.slots.lock().unwrap().size -= 1;
-            self.pool.manager.detach(&mut inner.obj);
+        if let Some(stage) = self.stage.take() {
+            let mut slots = self.pool.slots.lock().unwrap();
+            slots.leave(stage);
+            let add_permit = slots.add_permit(stage);
+            slots.size -= 1;
+            drop(slots);
+            if add_permit {
+                self.pool.semaphore.add_permits(1);
+            }
+            if let Some(mut inner) = self.inner.take() {
+                self.pool.manager.detach(&mut inner.obj);
+                self.pool.observers.detach(&inner.metrics);
+                self.pool.stats.detached();
+                self.pool.notify_replenish();
+            }
+            self.pool.status_changed();
         }
     }
 }
@@ -183,6 +343,19 @@ pub(crate) struct ObjectInner<M: Manager> {
 
     /// Object metrics.
     metrics: Metrics,
+
+    /// Generation of the [`Pool`] this object was created in. Objects of
+    /// previous generations are detached instead of being returned to the
+    /// [`Pool`]. See [`Pool::invalidate_all()`].
+    generation: u64,
+
+    /// Indicates whether the object has been used since it was last
+    /// recycled. See [`RecycleMode::Background`].
+    dirty: bool,
+
+    /// Permit of the [`PoolBudget`] of the [`Pool`] if it has one. It's only
+    /// held for giving it back once the object is dropped.
+    _budget: Option<budget::BudgetPermit>,
 }
 
 impl<M: Manager> Object<M> {
@@ -190,11 +363,31 @@ impl<M: Manager> Object<M> {
     /// size of the [`Pool`].
     #[must_use]
     pub fn take(mut this: Self) -> M::Type {
-        let mut inner = this.inner.take().unwrap().obj;
+        let mut inner = this.inner.take().unwrap();
         if let Some(pool) = Object::pool(&this) {
-            pool.inner.detach_object(&mut inner)
+            pool.inner.checkin(this.checkout_id);
+            pool.inner.detach_object(&mut inner);
+            pool.inner.stats.taken();
         }
-        inner
+        inner.obj
+    }
+
+    /// Marks this [`Object`] as broken.
+    ///
+    /// Broken objects aren't returned to their [`Pool`] when being dropped.
+    /// Instead they are detached via [`Manager::detach()`] and free their
+    /// slot of the [`Pool`] so a new object can be created in their place.
+    /// Use this after encountering an error which leaves the object in an
+    /// unusable state that [`Manager::recycle()`] might not detect.
+    pub fn mark_broken(this: &mut Self) {
+        this.broken = true;
+    }
+
+    /// Indicates whether this [`Object`] has been marked as broken using
+    /// [`Object::mark_broken()`].
+    #[must_use]
+    pub fn is_broken(this: &Self) -> bool {
+        this.broken
     }
 
     /// Get object statistics
@@ -216,9 +409,15 @@ impl<M: Manager> Object<M> {
 
 impl<M: Manager> Drop for Object<M> {
     fn drop(&mut self) {
-        if let Some(inner) = self.inner.take() {
+        if let Some(mut inner) = self.inner.take() {
             if let Some(pool) = self.pool.upgrade() {
-                pool.return_object(inner)
+                pool.checkin(self.checkout_id);
+                if self.broken {
+                    pool.detach_object(&mut inner);
+                    pool.stats.detached();
+                } else {
+                    pool.return_object(inner)
+                }
             }
         }
     }
@@ -290,23 +489,88 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
         PoolBuilder::new(manager)
     }
 
-    pub(crate) fn from_builder(builder: PoolBuilder<M, W>) -> Self {
-        Self {
+    pub(crate) fn from_builder(builder: PoolBuilder<M, W>) -> Self
+    where
+        M: 'static,
+    {
+        let replenish = Arc::new(Notify::new());
+        #[allow(unused_mut)]
+        let mut observers = builder.observers;
+        #[cfg(feature = "metrics")]
+        observers.push(Box::new(exporter::MetricsExporter::new(
+            builder.name.as_deref(),
+        )));
+        let pool = Self {
             inner: Arc::new(PoolInner {
                 manager: builder.manager,
                 slots: Mutex::new(Slots {
                     vec: VecDeque::with_capacity(builder.config.max_size),
                     size: 0,
                     max_size: builder.config.max_size,
+                    in_use: 0,
+                    creating: 0,
+                    recycling: 0,
+                    waiting: 0,
+                    draining: false,
+                    generation: 0,
                 }),
-                users: AtomicUsize::new(0),
                 semaphore: Semaphore::new(builder.config.max_size),
+                queue: queue::WaitQueue::new(&builder.config),
+                circuit_breaker: builder
+                    .config
+                    .circuit_breaker
+                    .map(circuit::CircuitBreaker::new),
                 config: builder.config,
                 hooks: builder.hooks,
+                observers,
+                name: builder.name,
                 runtime: builder.runtime,
+                replenish: replenish.clone(),
+                stats: StatsCounters::default(),
+                leak_detector: builder
+                    .config
+                    .leak_detection_threshold
+                    .map(leak::LeakDetector::new),
+                drained: Notify::new(),
+                pause: pause::PauseState::new(),
+                budget: builder.budget,
+                shared: shared::SharedObjects::new(),
+                recycle_on_return: match (builder.config.recycle_mode, builder.runtime) {
+                    (RecycleMode::OnReturn, Some(runtime)) => {
+                        Some(recycler::recycle_on_return(runtime))
+                    }
+                    _ => None,
+                },
+                object_selector: builder.object_selector,
             }),
             _wrapper: PhantomData::default(),
+        };
+        if let Some(budget) = &pool.inner.budget {
+            let member: Arc<dyn budget::Member> = pool.inner.clone();
+            budget.register(Arc::downgrade(&member));
+        }
+        if let (Some(runtime), true) = (pool.inner.runtime, pool.inner.config.min_idle > 0) {
+            replenish::spawn(runtime, Arc::downgrade(&pool.inner), replenish);
         }
+        let config = &pool.inner.config;
+        let reap_interval = match (config.max_lifetime, config.idle_timeout) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if let (Some(runtime), Some(interval)) = (pool.inner.runtime, reap_interval) {
+            reaper::spawn(runtime, Arc::downgrade(&pool.inner), interval);
+        }
+        if let (Some(runtime), Some(threshold)) =
+            (pool.inner.runtime, config.leak_detection_threshold)
+        {
+            leak::spawn(runtime, Arc::downgrade(&pool.inner), threshold);
+        }
+        if let (Some(runtime), RecycleMode::Background { interval }) =
+            (pool.inner.runtime, config.recycle_mode)
+        {
+            recycler::spawn(runtime, Arc::downgrade(&pool.inner), interval);
+        }
+        pool
     }
 
     /// Retrieves an [`Object`] from this [`Pool`] or waits for one to
@@ -315,8 +579,24 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
     /// # Errors
     ///
     /// See [`PoolError`] for details.
-    pub async fn get(&self) -> Result<W, PoolError<M::Error>> {
-        self.timeout_get(&self.timeouts()).await
+    #[track_caller]
+    pub fn get(&self) -> impl Future<Output = Result<W, PoolError<M::Error>>> + '_ {
+        self.timeout_get_with_priority(&self.timeouts(), Priority::Normal)
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] or waits for one to
+    /// become available. Tasks waiting for an [`Object`] are served by their
+    /// [`Priority`] first and in FIFO order second.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    #[track_caller]
+    pub fn get_with_priority(
+        &self,
+        priority: Priority,
+    ) -> impl Future<Output = Result<W, PoolError<M::Error>>> + '_ {
+        self.timeout_get_with_priority(&self.timeouts(), priority)
     }
 
     /// Retrieves an [`Object`] from this [`Pool`] and doesn't wait if there is
@@ -330,12 +610,12 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
         since = "0.9.3",
         note = "The name of this method is highly misleading. Please use timeout_get instead. e.g.\n`pool.timeout_get(&Timeouts { wait: Some(Duration::ZERO), ..pool.timeouts() })`"
     )]
-    pub async fn try_get(&self) -> Result<W, PoolError<M::Error>> {
+    #[track_caller]
+    pub fn try_get(&self) -> impl Future<Output = Result<W, PoolError<M::Error>>> + '_ {
         self.timeout_get(&Timeouts {
             wait: Some(Duration::ZERO),
             ..self.timeouts()
         })
-        .await
     }
 
     /// Retrieves an [`Object`] from this [`Pool`] using a different `timeout`
@@ -344,125 +624,494 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
     /// # Errors
     ///
     /// See [`PoolError`] for details.
-    pub async fn timeout_get(&self, timeouts: &Timeouts) -> Result<W, PoolError<M::Error>> {
-        let _ = self.inner.users.fetch_add(1, Ordering::Relaxed);
-        let users_guard = DropGuard(|| {
-            let _ = self.inner.users.fetch_sub(1, Ordering::Relaxed);
+    #[track_caller]
+    pub fn timeout_get<'a>(
+        &'a self,
+        timeouts: &Timeouts,
+    ) -> impl Future<Output = Result<W, PoolError<M::Error>>> + 'a {
+        self.timeout_get_with_priority(timeouts, Priority::Normal)
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] using a different `timeout`
+    /// than the configured one. Tasks waiting for an [`Object`] are served by
+    /// their [`Priority`] first and in FIFO order second.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    #[track_caller]
+    pub fn timeout_get_with_priority<'a>(
+        &'a self,
+        timeouts: &Timeouts,
+        priority: Priority,
+    ) -> impl Future<Output = Result<W, PoolError<M::Error>>> + 'a {
+        // The caller is captured here as `#[track_caller]` has no effect on
+        // `async fn`s.
+        self.checkout(*timeouts, priority, Location::caller())
+    }
+
+    /// Retrieves `n` [`Object`]s from this [`Pool`] at once or waits for
+    /// them to become available.
+    ///
+    /// The slots for all `n` [`Object`]s are acquired in a single operation,
+    /// so tasks needing multiple [`Object`]s at the same time can't deadlock
+    /// each other by holding some of them while waiting for the rest. If
+    /// retrieving any of the [`Object`]s fails, the ones retrieved already
+    /// are returned to this [`Pool`].
+    ///
+    /// # Errors
+    ///
+    /// If `n` exceeds the [`PoolConfig::max_size`] minus the
+    /// [`PoolConfig::high_priority_reserve`] a
+    /// [`PoolError::TooManyObjects`] is returned immediately. See
+    /// [`PoolError`] for other errors.
+    #[track_caller]
+    pub fn get_many(
+        &self,
+        n: usize,
+    ) -> impl Future<Output = Result<Vec<W>, PoolError<M::Error>>> + '_ {
+        self.timeout_get_many(n, &self.timeouts())
+    }
+
+    /// Retrieves `n` [`Object`]s from this [`Pool`] at once using a different
+    /// `timeout` than the configured one. See [`Pool::get_many()`] for
+    /// details.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    #[track_caller]
+    pub fn timeout_get_many<'a>(
+        &'a self,
+        n: usize,
+        timeouts: &Timeouts,
+    ) -> impl Future<Output = Result<Vec<W>, PoolError<M::Error>>> + 'a {
+        self.checkout_many(n, *timeouts, Location::caller())
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] which may be lent to up to
+    /// [`PoolConfig::max_shared_users`] users concurrently, e.g. a
+    /// connection of a protocol which supports multiplexing.
+    ///
+    /// The [`Object`] with the least users is chosen. A new [`Object`] is
+    /// only retrieved from this [`Pool`] if all shared [`Object`]s already
+    /// have the maximum number of users. Once the last user drops its
+    /// [`SharedObject`], the [`Object`] is returned to this [`Pool`] and
+    /// recycled before being handed out again.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    #[track_caller]
+    pub fn get_shared(
+        &self,
+    ) -> impl Future<Output = Result<SharedObject<M>, PoolError<M::Error>>> + '_
+    where
+        M: 'static,
+        M::Type: Sync,
+    {
+        let location = Location::caller();
+        async move {
+            // Closed and paused pools are handled by `get_object`.
+            if !self.is_closed() && !self.is_paused() {
+                let max_users = self.inner.config.max_shared_users;
+                let generation = self.inner.slots.lock().unwrap().generation;
+                if let Some(obj) = self.inner.shared.lend(max_users, generation) {
+                    return Ok(obj);
+                }
+            }
+            let obj = self
+                .get_object(&self.timeouts(), Priority::Normal, location)
+                .await?;
+            Ok(self.inner.shared.share(obj))
+        }
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "timeout_get",
+            target = "deadpool",
+            level = "debug",
+            skip_all,
+            fields(
+                pool = self.name().unwrap_or_default(),
+                queue_mode = ?self.inner.config.queue_mode,
+                priority = ?priority,
+                outcome = tracing::field::Empty,
+            ),
+        )
+    )]
+    async fn checkout(
+        &self,
+        timeouts: Timeouts,
+        priority: Priority,
+        location: &'static Location<'static>,
+    ) -> Result<W, PoolError<M::Error>> {
+        let result = self.get_object(&timeouts, priority, location).await;
+        #[cfg(feature = "tracing")]
+        let _ = tracing::Span::current().record("outcome", trace::outcome(&result));
+        result.map(Into::into)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "timeout_get_many",
+            target = "deadpool",
+            level = "debug",
+            skip_all,
+            fields(
+                pool = self.name().unwrap_or_default(),
+                queue_mode = ?self.inner.config.queue_mode,
+                n = n,
+                outcome = tracing::field::Empty,
+            ),
+        )
+    )]
+    async fn checkout_many(
+        &self,
+        n: usize,
+        timeouts: Timeouts,
+        location: &'static Location<'static>,
+    ) -> Result<Vec<W>, PoolError<M::Error>> {
+        let result = self
+            .get_objects(n, &timeouts, Priority::Normal, location)
+            .await;
+        #[cfg(feature = "tracing")]
+        let _ = tracing::Span::current().record("outcome", trace::outcome(&result));
+        result.map(|objs| objs.into_iter().map(Into::into).collect())
+    }
+
+    async fn get_object(
+        &self,
+        timeouts: &Timeouts,
+        priority: Priority,
+        location: &'static Location<'static>,
+    ) -> Result<Object<M>, PoolError<M::Error>> {
+        let permit = self.acquire_permits(timeouts, priority, 1).await?;
+        let obj = self.ready_object(timeouts, location).await?;
+        permit.forget();
+        Ok(obj)
+    }
+
+    async fn get_objects(
+        &self,
+        n: usize,
+        timeouts: &Timeouts,
+        priority: Priority,
+        location: &'static Location<'static>,
+    ) -> Result<Vec<Object<M>>, PoolError<M::Error>> {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        // Waiting for more permits than the semaphore ever has would never
+        // finish.
+        let max_size = self.inner.slots.lock().unwrap().max_size;
+        let max_n = match priority {
+            Priority::High => max_size,
+            Priority::Normal | Priority::Low => {
+                max_size.saturating_sub(self.inner.config.high_priority_reserve)
+            }
+        };
+        if n > max_n {
+            return Err(PoolError::TooManyObjects);
+        }
+        let permits = n.min(u32::MAX as usize) as u32;
+        self.acquire_permits(timeouts, priority, permits)
+            .await?
+            .forget();
+        // Every permit which hasn't been handed out along with an object yet
+        // is given back if this fails or is cancelled. The permits of the
+        // objects which are ready already are given back by dropping them.
+        let remaining = AtomicUsize::new(n);
+        let _remaining_guard = DropGuard(|| {
+            self.inner
+                .semaphore
+                .add_permits(remaining.load(Ordering::Relaxed));
+        });
+        let mut objs = Vec::with_capacity(n);
+        while objs.len() < n {
+            objs.push(self.ready_object(timeouts, location).await?);
+            let _ = remaining.fetch_sub(1, Ordering::Relaxed);
+        }
+        Ok(objs)
+    }
+
+    /// Acquires `n` permits of the [`Semaphore`] for retrieving objects.
+    async fn acquire_permits(
+        &self,
+        timeouts: &Timeouts,
+        priority: Priority,
+        n: u32,
+    ) -> Result<SemaphorePermit<'_>, PoolError<M::Error>> {
+        self.inner.slots.lock().unwrap().waiting += 1;
+        let waiting_guard = DropGuard(|| {
+            self.inner.slots.lock().unwrap().waiting -= 1;
+            self.inner.status_changed();
         });
+        self.inner.status_changed();
 
         let non_blocking = match timeouts.wait {
             Some(t) => t.as_nanos() == 0,
             None => false,
         };
 
+        let wait_start = Instant::now();
         let permit = if non_blocking {
-            self.inner.semaphore.try_acquire().map_err(|e| match e {
-                TryAcquireError::Closed => PoolError::Closed,
-                TryAcquireError::NoPermits => PoolError::Timeout(TimeoutType::Wait),
-            })?
+            match self.inner.pause.get() {
+                Some(_) if self.inner.semaphore.is_closed() => Err(PoolError::Closed),
+                Some(options) if options.fail_fast => Err(PoolError::Paused),
+                Some(_) => Err(PoolError::Timeout(TimeoutType::Wait)),
+                None => self
+                    .inner
+                    .queue
+                    .try_acquire(&self.inner.semaphore, priority, n)
+                    .map_err(|e| match e {
+                        TryAcquireError::Closed => PoolError::Closed,
+                        TryAcquireError::NoPermits => PoolError::Timeout(TimeoutType::Wait),
+                    }),
+            }
         } else {
-            apply_timeout(
+            let acquire = apply_timeout(
                 self.inner.runtime,
                 TimeoutType::Wait,
                 timeouts.wait,
                 async {
-                    self.inner
-                        .semaphore
-                        .acquire()
-                        .await
-                        .map_err(|_| PoolError::Closed)
+                    loop {
+                        self.inner
+                            .pause
+                            .wait_resumed(&self.inner.semaphore, &self.inner.queue)
+                            .await?;
+                        let permit = self
+                            .inner
+                            .queue
+                            .acquire(&self.inner.semaphore, priority, n)
+                            .await
+                            .map_err(|e| match e {
+                                queue::AcquireError::Closed => PoolError::Closed,
+                                queue::AcquireError::QueueFull => PoolError::QueueFull,
+                            })?;
+                        // The pool might have been paused while waiting.
+                        if self.inner.pause.get().is_none() {
+                            break Ok::<_, PoolError<M::Error>>(permit);
+                        }
+                    }
                 },
-            )
-            .await?
+            );
+            #[cfg(feature = "tracing")]
+            let acquire = tracing::Instrument::instrument(
+                acquire,
+                tracing::debug_span!(target: "deadpool", "wait"),
+            );
+            acquire.await
         };
+        drop(waiting_guard);
+        let permit = match permit {
+            Ok(permit) => permit,
+            Err(e) => {
+                if let PoolError::Timeout(timeout_type) = e {
+                    self.inner.observers.timeout(timeout_type);
+                    self.inner.timeout(timeout_type);
+                }
+                return Err(e);
+            }
+        };
+        let wait_duration = wait_start.elapsed();
+        self.inner.observers.checkout_wait(wait_duration);
+        self.inner.stats.waited(wait_duration);
+        Ok(permit)
+    }
 
-        let inner_obj = loop {
-            let inner_obj = match self.inner.config.queue_mode {
-                QueueMode::Fifo => self.inner.slots.lock().unwrap().vec.pop_front(),
-                QueueMode::Lifo => self.inner.slots.lock().unwrap().vec.pop_back(),
-            };
-            let inner_obj = if let Some(inner_obj) = inner_obj {
-                self.try_recycle(timeouts, inner_obj).await?
-            } else {
-                self.try_create(timeouts).await?
+    /// Recycles an idle object or creates a new one and hands it out. The
+    /// caller must hold a permit of the [`Semaphore`] for it.
+    async fn ready_object(
+        &self,
+        timeouts: &Timeouts,
+        location: &'static Location<'static>,
+    ) -> Result<Object<M>, PoolError<M::Error>> {
+        let inner = loop {
+            let unready_obj = self.inner.pop_idle();
+            self.inner.notify_replenish();
+            let unready_obj = match (unready_obj, self.inner.config.recycle_mode) {
+                (Some(unready_obj), RecycleMode::OnCheckout) => {
+                    self.try_recycle(timeouts, unready_obj).await?
+                }
+                // Recycled after being returned already
+                (Some(mut unready_obj), _) => {
+                    if self.inner.is_expired(&unready_obj.inner().metrics) {
+                        None
+                    } else {
+                        Some(unready_obj)
+                    }
+                }
+                (None, _) => Some(self.try_create(timeouts).await?),
             };
-            if let Some(inner_obj) = inner_obj {
-                break inner_obj;
+            if let Some(inner) = unready_obj.and_then(UnreadyObject::into_in_use) {
+                break inner;
             }
         };
 
-        users_guard.disarm();
-        permit.forget();
-
+        let checkout_id = self.inner.leak_detector.as_ref().map(|leak_detector| {
+            leak_detector.checkout(CheckoutInfo {
+                location,
+                checked_out: Instant::now(),
+                metrics: inner.metrics,
+            })
+        });
         Ok(Object {
-            inner: Some(inner_obj),
+            inner: Some(inner),
             pool: Arc::downgrade(&self.inner),
-        }
-        .into())
+            checkout_id,
+            broken: false,
+        })
     }
 
     #[inline]
-    async fn try_recycle(
-        &self,
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(target = "deadpool", level = "debug", skip_all)
+    )]
+    async fn try_recycle<'a>(
+        &'a self,
         timeouts: &Timeouts,
-        inner_obj: ObjectInner<M>,
-    ) -> Result<Option<ObjectInner<M>>, PoolError<M::Error>> {
-        let mut unready_obj = UnreadyObject {
-            inner: Some(inner_obj),
-            pool: &self.inner,
-        };
+        mut unready_obj: UnreadyObject<'a, M>,
+    ) -> Result<Option<UnreadyObject<'a, M>>, PoolError<M::Error>> {
         let inner = unready_obj.inner();
 
-        // Apply pre_recycle hooks
-        if let Err(_e) = self.inner.hooks.pre_recycle.apply(inner).await {
-            // TODO log pre_recycle error
+        // Reject expired objects before even trying to recycle them
+        if self.inner.is_expired(&inner.metrics) {
             return Ok(None);
         }
 
-        if apply_timeout(
-            self.inner.runtime,
-            TimeoutType::Recycle,
-            timeouts.recycle,
-            self.inner.manager.recycle(&mut inner.obj, &inner.metrics),
-        )
-        .await
-        .is_err()
-        {
+        // Apply pre_recycle hooks
+        if let Err(e) = self.inner.hooks.pre_recycle.apply(inner).await {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                target: "deadpool",
+                pool = self.name().unwrap_or_default(),
+                "`pre_recycle` hook failed: {}",
+                trace::hook_error(&e),
+            );
+            self.inner.observers.hook_failure(HookType::PreRecycle, &e);
             return Ok(None);
         }
 
+        // Objects which have been recycled recently are most likely fine
+        let recycle = !self.inner.is_recently_recycled(&inner.metrics);
+        if recycle {
+            let recycle_start = Instant::now();
+            match apply_timeout(
+                self.inner.runtime,
+                TimeoutType::Recycle,
+                timeouts.recycle,
+                self.inner.manager.recycle(&mut inner.obj, &inner.metrics),
+            )
+            .await
+            {
+                Ok(()) => {
+                    self.inner
+                        .observers
+                        .recycle_success(recycle_start.elapsed());
+                    self.inner.stats.recycled();
+                }
+                Err(PoolError::Backend(e)) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        target: "deadpool",
+                        pool = self.name().unwrap_or_default(),
+                        "Recycling object failed: {}",
+                        trace::recycle_error(&e),
+                    );
+                    self.inner
+                        .observers
+                        .recycle_failure(recycle_start.elapsed(), &e);
+                    self.inner.stats.recycle_error();
+                    return Ok(None);
+                }
+                Err(PoolError::Timeout(timeout_type)) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        target: "deadpool",
+                        pool = self.name().unwrap_or_default(),
+                        "Recycling object failed: Timeout",
+                    );
+                    self.inner.observers.timeout(timeout_type);
+                    self.inner.timeout(timeout_type);
+                    return Ok(None);
+                }
+                Err(_) => return Ok(None),
+            }
+        }
+
         // Apply post_recycle hooks
-        if let Err(_e) = self.inner.hooks.post_recycle.apply(inner).await {
-            // TODO log post_recycle error
+        if let Err(e) = self.inner.hooks.post_recycle.apply(inner).await {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                target: "deadpool",
+                pool = self.name().unwrap_or_default(),
+                "`post_recycle` hook failed: {}",
+                trace::hook_error(&e),
+            );
+            self.inner.observers.hook_failure(HookType::PostRecycle, &e);
             return Ok(None);
         }
 
-        inner.metrics.recycle_count += 1;
-        inner.metrics.recycled = Some(Instant::now());
+        if recycle {
+            inner.metrics.recycle_count += 1;
+            inner.metrics.recycled = Some(Instant::now());
+        }
+        inner.dirty = false;
 
-        Ok(Some(unready_obj.ready()))
+        Ok(Some(unready_obj))
     }
 
     #[inline]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(target = "deadpool", level = "debug", skip_all)
+    )]
     async fn try_create(
         &self,
         timeouts: &Timeouts,
-    ) -> Result<Option<ObjectInner<M>>, PoolError<M::Error>> {
-        let mut unready_obj = UnreadyObject {
-            inner: Some(ObjectInner {
-                obj: apply_timeout(
-                    self.inner.runtime,
-                    TimeoutType::Create,
-                    timeouts.create,
-                    self.inner.manager.create(),
-                )
-                .await?,
-                metrics: Metrics::default(),
-            }),
-            pool: &self.inner,
+    ) -> Result<UnreadyObject<'_, M>, PoolError<M::Error>> {
+        let mut unready_obj = UnreadyObject::create(&self.inner);
+        let attempt = match &self.inner.circuit_breaker {
+            Some(circuit_breaker) => match circuit_breaker.attempt() {
+                Some(attempt) => Some(attempt),
+                None => return Err(PoolError::CircuitOpen),
+            },
+            None => None,
         };
-
-        self.inner.slots.lock().unwrap().size += 1;
+        let (budget, timeouts) = match &self.inner.budget {
+            Some(budget) => self.acquire_budget(budget, timeouts).await?,
+            None => (None, *timeouts),
+        };
+        let obj = match self.create_with_retry(&timeouts).await {
+            Ok(obj) => {
+                if let Some(attempt) = attempt {
+                    if attempt.success() {
+                        self.inner.status_changed();
+                    }
+                }
+                obj
+            }
+            Err(e) => {
+                if let Some(attempt) = attempt {
+                    if attempt.failure() {
+                        self.inner.status_changed();
+                    }
+                }
+                return Err(e);
+            }
+        };
+        unready_obj.inner = Some(ObjectInner {
+            obj,
+            metrics: Metrics::default(),
+            generation: unready_obj.generation,
+            dirty: false,
+            _budget: budget,
+        });
 
         // Apply post_create hooks
         if let Err(e) = self
@@ -472,10 +1121,159 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
             .apply(unready_obj.inner())
             .await
         {
+            self.inner.observers.hook_failure(HookType::PostCreate, &e);
             return Err(PoolError::PostCreateHook(e));
         }
 
-        Ok(Some(unready_obj.ready()))
+        Ok(unready_obj)
+    }
+
+    /// Acquires a permit of the given [`PoolBudget`] and returns it along
+    /// with the `timeouts` which are left for creating the object.
+    async fn acquire_budget(
+        &self,
+        budget: &PoolBudget,
+        timeouts: &Timeouts,
+    ) -> Result<(Option<budget::BudgetPermit>, Timeouts), PoolError<M::Error>> {
+        let start = Instant::now();
+        let result = apply_timeout(
+            self.inner.runtime,
+            TimeoutType::Create,
+            timeouts.create,
+            async { Ok::<_, PoolError<M::Error>>(budget.acquire(&*self.inner).await) },
+        )
+        .await;
+        match result {
+            Ok(permit) => {
+                let timeouts = Timeouts {
+                    create: timeouts
+                        .create
+                        .map(|timeout| timeout.saturating_sub(start.elapsed())),
+                    ..*timeouts
+                };
+                Ok((Some(permit), timeouts))
+            }
+            Err(e) => {
+                if let PoolError::Timeout(timeout_type) = e {
+                    self.inner.observers.timeout(timeout_type);
+                    self.inner.timeout(timeout_type);
+                }
+                self.inner.observers.create_failure(start.elapsed(), &e);
+                self.inner.stats.create_error();
+                Err(e)
+            }
+        }
+    }
+
+    /// Calls [`Manager::create()`] and retries failed attempts according to
+    /// the [`PoolConfig::create_retry`] policy.
+    async fn create_with_retry(&self, timeouts: &Timeouts) -> Result<M::Type, PoolError<M::Error>> {
+        let deadline = timeouts.create.map(|timeout| Instant::now() + timeout);
+        let mut attempt = 1;
+        loop {
+            // The error must not be held across the `sleep()` as
+            // `Manager::Error` isn't required to be `Send`.
+            let (runtime, backoff) = {
+                let create_start = Instant::now();
+                let e = match apply_timeout(
+                    self.inner.runtime,
+                    TimeoutType::Create,
+                    deadline.map(|deadline| deadline.saturating_duration_since(create_start)),
+                    self.inner.manager.create(),
+                )
+                .await
+                {
+                    Ok(obj) => {
+                        self.inner.observers.create_success(create_start.elapsed());
+                        self.inner.stats.created();
+                        return Ok(obj);
+                    }
+                    Err(e) => e,
+                };
+                if let PoolError::Timeout(timeout_type) = e {
+                    self.inner.observers.timeout(timeout_type);
+                    self.inner.timeout(timeout_type);
+                }
+                self.inner
+                    .observers
+                    .create_failure(create_start.elapsed(), &e);
+                self.inner.stats.create_error();
+                self.retry_backoff(attempt, deadline, e)?
+            };
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                target: "deadpool",
+                pool = self.name().unwrap_or_default(),
+                attempt,
+                ?backoff,
+                "Creating object failed, retrying",
+            );
+            runtime.sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+
+    /// Returns the time to wait before retrying to create an object after
+    /// the given failed `attempt` or the error if it shouldn't be retried.
+    fn retry_backoff(
+        &self,
+        attempt: u32,
+        deadline: Option<Instant>,
+        error: PoolError<M::Error>,
+    ) -> Result<(Runtime, Duration), PoolError<M::Error>> {
+        let (retry, runtime) = match (&self.inner.config.create_retry, self.inner.runtime) {
+            (Some(retry), Some(runtime)) if attempt < retry.max_attempts => (retry, runtime),
+            _ => return Err(error),
+        };
+        match &error {
+            PoolError::Backend(e) if self.inner.manager.is_retryable(e) => {}
+            _ => return Err(error),
+        }
+        let backoff = retry::backoff(retry, attempt);
+        if let Some(deadline) = deadline {
+            if Instant::now() + backoff >= deadline {
+                return Err(error);
+            }
+        }
+        Ok((runtime, backoff))
+    }
+
+    /// Pre-creates up to `count` objects concurrently and adds them to this
+    /// [`Pool`] as idle objects.
+    ///
+    /// This is typically used at startup so the [`Pool`] holds ready objects
+    /// before the first [`Pool::get()`] call. The [`Timeouts::create`] of the
+    /// [`Pool`] and the `post_create` hooks are applied to every object.
+    ///
+    /// The number of created objects is limited by the free capacity of the
+    /// [`Pool`]. Failing attempts don't stop the other ones but are reported
+    /// via the returned [`WarmUpReport`].
+    pub async fn warm_up(&self, count: usize) -> WarmUpReport<M::Error> {
+        let count = {
+            let slots = self.inner.slots.lock().unwrap();
+            count.min(slots.max_size.saturating_sub(slots.size))
+        };
+        let mut permits = Vec::with_capacity(count);
+        for _ in 0..count {
+            match self.inner.semaphore.try_acquire() {
+                Ok(permit) => permits.push(permit),
+                Err(_) => break,
+            }
+        }
+        let timeouts = self.timeouts();
+        let results = join::join_all(permits.iter().map(|_| self.try_create(&timeouts))).await;
+        let mut report = WarmUpReport::default();
+        for result in results {
+            match result {
+                Ok(unready_obj) => {
+                    unready_obj.into_idle();
+                    report.created += 1;
+                }
+                Err(e) => report.errors.push(e),
+            }
+        }
+        drop(permits);
+        report
     }
 
     /**
@@ -510,6 +1308,7 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
                 vec.push_back(obj);
             }
             slots.vec = vec;
+            self.inner.notify_replenish();
         }
         // grow pool
         if max_size > old_max_size {
@@ -517,6 +1316,8 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
             slots.vec.reserve_exact(additional);
             self.inner.semaphore.add_permits(additional);
         }
+        drop(slots);
+        self.inner.status_changed();
     }
 
     /// Retains only the objects specified by the given function.
@@ -551,10 +1352,39 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
                 true
             } else {
                 self.manager().detach(&mut obj.obj);
+                self.inner.observers.detach(&obj.metrics);
+                self.inner.stats.detached();
                 false
             }
         });
         guard.size -= len_before - guard.vec.len();
+        drop(guard);
+        self.inner.notify_replenish();
+        self.inner.status_changed();
+    }
+
+    /// Invalidates all [`Object`]s of this [`Pool`] so they are replaced by
+    /// new ones, e.g. after rotating credentials.
+    ///
+    /// Idle [`Object`]s are detached immediately while [`Object`]s which are
+    /// currently checked out are detached once they are returned to the
+    /// [`Pool`]. [`Object`]s which are being created or recycled at the
+    /// moment are detached instead of being handed out.
+    pub fn invalidate_all(&self) {
+        let objs = {
+            let mut slots = self.inner.slots.lock().unwrap();
+            slots.generation += 1;
+            let objs = slots.vec.drain(..).collect::<Vec<_>>();
+            slots.size -= objs.len();
+            objs
+        };
+        for mut inner in objs {
+            self.inner.manager.detach(&mut inner.obj);
+            self.inner.observers.detach(&inner.metrics);
+            self.inner.stats.detached();
+        }
+        self.inner.notify_replenish();
+        self.inner.status_changed();
     }
 
     /// Get current timeout configuration
@@ -567,10 +1397,90 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
     /// All current and future tasks waiting for [`Object`]s will return
     /// [`PoolError::Closed`] immediately.
     ///
-    /// This operation resizes the pool to 0.
+    /// This operation resizes the pool to 0 and stops the background task
+    /// maintaining [`PoolConfig::min_idle`] objects.
     pub fn close(&self) {
         self.resize(0);
         self.inner.semaphore.close();
+        self.inner.pause.notify_closed();
+        self.inner.replenish.notify_one();
+    }
+
+    /// Closes this [`Pool`] gracefully.
+    ///
+    /// Just like [`Pool::close()`] this makes all current and future tasks
+    /// waiting for [`Object`]s return [`PoolError::Closed`]. Additionally it
+    /// waits for all [`Object`]s to be returned to the [`Pool`] and calls
+    /// [`Manager::destroy()`] for every object.
+    ///
+    /// If a `timeout` is given and not all [`Object`]s have been returned
+    /// within that time, a [`PoolError::Timeout`] with
+    /// [`TimeoutType::Wait`] is returned. [`Object`]s returned afterwards are
+    /// dropped without calling [`Manager::destroy()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn close_gracefully(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<(), PoolError<M::Error>> {
+        if timeout.is_some() && self.inner.runtime.is_none() {
+            return Err(PoolError::NoRuntimeSpecified);
+        }
+        {
+            let mut slots = self.inner.slots.lock().unwrap();
+            slots.max_size = 0;
+            slots.draining = true;
+        }
+        self.inner.semaphore.close();
+        self.inner.pause.notify_closed();
+        self.inner.replenish.notify_one();
+        let result = apply_timeout(self.inner.runtime, TimeoutType::Wait, timeout, async {
+            self.drain().await;
+            Ok::<_, PoolError<M::Error>>(())
+        })
+        .await;
+        // Objects returned after the timeout are simply dropped.
+        let objs = {
+            let mut slots = self.inner.slots.lock().unwrap();
+            slots.draining = false;
+            let objs = slots.vec.drain(..).collect::<Vec<_>>();
+            slots.size -= objs.len();
+            objs
+        };
+        for mut inner in objs {
+            self.inner.manager.detach(&mut inner.obj);
+            self.inner.observers.detach(&inner.metrics);
+            self.inner.stats.detached();
+        }
+        self.inner.status_changed();
+        result
+    }
+
+    /// Destroys idle objects until all objects have been destroyed.
+    async fn drain(&self) {
+        loop {
+            let (objs, drained) = {
+                let mut slots = self.inner.slots.lock().unwrap();
+                let objs = slots.vec.drain(..).collect::<Vec<_>>();
+                slots.size -= objs.len();
+                (objs, slots.size == 0)
+            };
+            if !objs.is_empty() {
+                self.inner.status_changed();
+            }
+            for mut inner in objs {
+                self.inner.manager.detach(&mut inner.obj);
+                self.inner.observers.detach(&inner.metrics);
+                self.inner.stats.detached();
+                self.inner.manager.destroy(inner.obj).await;
+            }
+            if drained {
+                break;
+            }
+            self.inner.drained.notified().await;
+        }
     }
 
     /// Indicates whether this [`Pool`] has been closed.
@@ -578,24 +1488,67 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
         self.inner.semaphore.is_closed()
     }
 
+    /// Pauses this [`Pool`].
+    ///
+    /// Unlike [`Pool::close()`] this can be undone using [`Pool::resume()`].
+    /// While being paused no [`Object`]s are handed out. Tasks retrieving
+    /// [`Object`]s wait until the [`Pool`] is resumed, which is still
+    /// subject to the [`Timeouts::wait`] and [`PoolConfig::max_waiting`], or
+    /// fail with a [`PoolError::Paused`] if [`PauseOptions::fail_fast`] is
+    /// set. [`Object`]s which are already checked out are not affected.
+    pub fn pause(&self, options: PauseOptions) {
+        self.inner.pause.pause(options);
+        if options.drop_idle {
+            self.retain(|_, _| false);
+        }
+    }
+
+    /// Resumes this [`Pool`] after it has been paused using
+    /// [`Pool::pause()`].
+    pub fn resume(&self) {
+        self.inner.pause.resume();
+        self.inner.notify_replenish();
+    }
+
+    /// Indicates whether this [`Pool`] has been paused.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.inner.pause.get().is_some()
+    }
+
     /// Retrieves [`Status`] of this [`Pool`].
     #[must_use]
     pub fn status(&self) -> Status {
-        let slots = self.inner.slots.lock().unwrap();
-        let users = self.inner.users.load(Ordering::Relaxed);
-        let (available, waiting) = if users < slots.size {
-            (slots.size - users, 0)
-        } else {
-            (0, users - slots.size)
-        };
-        Status {
-            max_size: slots.max_size,
-            size: slots.size,
-            available,
-            waiting,
+        self.inner.status()
+    }
+
+    /// Retrieves the cumulative [`Stats`] of this [`Pool`].
+    #[must_use]
+    pub fn stats(&self) -> Stats {
+        self.inner.stats.snapshot()
+    }
+
+    /// Lists the [`Object`]s which are currently checked out of this
+    /// [`Pool`], the oldest one first.
+    ///
+    /// Checkouts are only tracked if a
+    /// [`PoolConfig::leak_detection_threshold`] is configured. Otherwise this
+    /// method always returns an empty [`Vec`].
+    #[must_use]
+    pub fn checked_out(&self) -> Vec<CheckoutInfo> {
+        match &self.inner.leak_detector {
+            Some(leak_detector) => leak_detector.checked_out(),
+            None => Vec::new(),
         }
     }
 
+    /// Returns the name of this [`Pool`] if one was set using
+    /// [`PoolBuilder::name()`].
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.inner.name.as_deref()
+    }
+
     /// Returns [`Manager`] of this [`Pool`].
     #[must_use]
     pub fn manager(&self) -> &M {
@@ -606,21 +1559,76 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
 struct PoolInner<M: Manager> {
     manager: M,
     slots: Mutex<Slots<ObjectInner<M>>>,
-    /// Number of available [`Object`]s in the [`Pool`]. If there are no
-    /// [`Object`]s in the [`Pool`] this number can become negative and store
-    /// the number of [`Future`]s waiting for an [`Object`].
-    users: AtomicUsize,
     semaphore: Semaphore,
+    /// Tasks waiting for a permit of the [`PoolInner::semaphore`].
+    queue: queue::WaitQueue,
+    circuit_breaker: Option<circuit::CircuitBreaker>,
     config: PoolConfig,
     runtime: Option<Runtime>,
     hooks: hooks::Hooks<M>,
+    observers: observer::Observers<M>,
+    name: Option<String>,
+    /// Wakes up the task maintaining [`PoolConfig::min_idle`] objects.
+    replenish: Arc<Notify>,
+    stats: StatsCounters,
+    /// Registry of checked out objects if a
+    /// [`PoolConfig::leak_detection_threshold`] is configured.
+    leak_detector: Option<leak::LeakDetector>,
+    /// Wakes up [`Pool::close_gracefully()`] whenever the [`Status`] of a
+    /// closed [`Pool`] changes.
+    drained: Notify,
+    pause: pause::PauseState,
+    budget: Option<PoolBudget>,
+    /// Objects lent to users via [`Pool::get_shared()`].
+    shared: shared::SharedObjects,
+    /// Spawns the task recycling a returned object if the
+    /// [`PoolConfig::recycle_mode`] is [`RecycleMode::OnReturn`].
+    recycle_on_return: Option<recycler::RecycleOnReturn<M>>,
+    /// Chooses the idle object handed out next instead of the
+    /// [`PoolConfig::queue_mode`].
+    object_selector: Option<Box<dyn ObjectSelector>>,
 }
 
 #[derive(Debug)]
 struct Slots<T> {
+    /// Idle objects.
     vec: VecDeque<T>,
+    /// Number of objects including the ones being created.
     size: usize,
     max_size: usize,
+    /// Number of objects handed out to users.
+    in_use: usize,
+    /// Number of objects being created.
+    creating: usize,
+    /// Number of objects being recycled.
+    recycling: usize,
+    /// Number of tasks waiting for a slot.
+    waiting: usize,
+    /// Indicates whether the [`Pool`] is being closed by
+    /// [`Pool::close_gracefully()`]. Returned objects are added to `vec`
+    /// regardless of the `max_size` so they can be destroyed.
+    draining: bool,
+    /// Current generation of the [`Pool`] which is incremented by
+    /// [`Pool::invalidate_all()`].
+    generation: u64,
+}
+
+impl<T> Slots<T> {
+    fn leave(&mut self, stage: Stage) {
+        match stage {
+            Stage::Creating => self.creating -= 1,
+            Stage::Recycling | Stage::Returning => self.recycling -= 1,
+        }
+    }
+    /// Indicates whether a semaphore permit needs to be added for an object
+    /// leaving the given [`Stage`]. This must be checked before the `size`
+    /// is decremented.
+    fn add_permit(&self, stage: Stage) -> bool {
+        match stage {
+            Stage::Returning => self.size <= self.max_size,
+            Stage::Creating | Stage::Recycling => false,
+        }
+    }
 }
 
 // Implemented manually to avoid unnecessary trait bound on the struct.
@@ -633,39 +1641,244 @@ where
         f.debug_struct("PoolInner")
             .field("manager", &self.manager)
             .field("slots", &self.slots)
-            .field("used", &self.users)
             .field("semaphore", &self.semaphore)
+            .field("queue", &self.queue)
+            .field("circuit_breaker", &self.circuit_breaker)
             .field("config", &self.config)
             .field("runtime", &self.runtime)
             .field("hooks", &self.hooks)
+            .field("observers", &self.observers)
+            .field("name", &self.name)
+            .field("replenish", &self.replenish)
+            .field("stats", &self.stats)
+            .field("leak_detector", &self.leak_detector)
+            .field("drained", &self.drained)
+            .field("pause", &self.pause)
+            .field("budget", &self.budget)
+            .field("shared", &self.shared)
+            .field("recycle_on_return", &self.recycle_on_return.is_some())
+            .field("object_selector", &self.object_selector.is_some())
             .finish()
     }
 }
 
 impl<M: Manager> PoolInner<M> {
-    fn return_object(&self, mut inner: ObjectInner<M>) {
-        let _ = self.users.fetch_sub(1, Ordering::Relaxed);
+    /// Removes the given checkout from the [`PoolInner::leak_detector`].
+    fn checkin(&self, checkout_id: Option<u64>) {
+        if let (Some(leak_detector), Some(id)) = (&self.leak_detector, checkout_id) {
+            leak_detector.checkin(id);
+        }
+    }
+    fn return_object(self: &Arc<Self>, mut inner: ObjectInner<M>) {
+        inner.dirty = true;
+        // Objects which aren't recycled on checkout would otherwise expire
+        // based on the time they were last recycled.
+        inner.metrics.returned = Some(Instant::now());
         let mut slots = self.slots.lock().unwrap();
+        if inner.generation != slots.generation {
+            drop(slots);
+            self.detach_object(&mut inner);
+            self.stats.detached();
+            return;
+        }
+        slots.in_use -= 1;
         if slots.size <= slots.max_size {
+            match &self.recycle_on_return {
+                Some(recycle_on_return) => {
+                    slots.recycling += 1;
+                    drop(slots);
+                    if let Err(mut inner) = recycle_on_return(self.clone(), inner) {
+                        // Objects which can't be recycled are detached.
+                        let mut slots = self.slots.lock().unwrap();
+                        slots.leave(Stage::Returning);
+                        let add_permit = slots.add_permit(Stage::Returning);
+                        slots.size -= 1;
+                        drop(slots);
+                        if add_permit {
+                            self.semaphore.add_permits(1);
+                        }
+                        self.manager.detach(&mut inner.obj);
+                        self.observers.detach(&inner.metrics);
+                        self.stats.detached();
+                        self.notify_replenish();
+                    }
+                }
+                None => {
+                    slots.vec.push_back(inner);
+                    drop(slots);
+                    self.semaphore.add_permits(1);
+                }
+            }
+        } else if slots.draining {
+            // Destroyed by `Pool::close_gracefully()`
             slots.vec.push_back(inner);
             drop(slots);
-            self.semaphore.add_permits(1);
         } else {
             slots.size -= 1;
             drop(slots);
             self.manager.detach(&mut inner.obj);
+            self.observers.detach(&inner.metrics);
+            self.stats.detached();
+            self.notify_replenish();
         }
+        self.status_changed();
+    }
+    /// Takes the first idle object which has been used since it was last
+    /// recycled. See [`RecycleMode::Background`].
+    fn pop_dirty(&self) -> Option<UnreadyObject<'_, M>> {
+        let mut slots = self.slots.lock().unwrap();
+        let index = slots.vec.iter().position(|obj| obj.dirty)?;
+        let inner = slots.vec.remove(index)?;
+        slots.recycling += 1;
+        drop(slots);
+        self.status_changed();
+        Some(UnreadyObject {
+            generation: inner.generation,
+            inner: Some(inner),
+            stage: Some(Stage::Recycling),
+            pool: self,
+        })
     }
-    fn detach_object(&self, obj: &mut M::Type) {
-        let _ = self.users.fetch_sub(1, Ordering::Relaxed);
+    /// Takes an idle object according to the [`PoolInner::object_selector`]
+    /// or the [`PoolConfig::queue_mode`] for recycling it.
+    fn pop_idle(&self) -> Option<UnreadyObject<'_, M>> {
         let mut slots = self.slots.lock().unwrap();
+        let inner = match &self.object_selector {
+            Some(object_selector) => select::take(&**object_selector, &mut slots.vec),
+            None => match self.config.queue_mode {
+                QueueMode::Fifo => slots.vec.pop_front(),
+                QueueMode::Lifo => slots.vec.pop_back(),
+                queue_mode => select::take(&queue_mode, &mut slots.vec),
+            },
+        }?;
+        slots.recycling += 1;
+        drop(slots);
+        self.status_changed();
+        Some(UnreadyObject {
+            generation: inner.generation,
+            inner: Some(inner),
+            stage: Some(Stage::Recycling),
+            pool: self,
+        })
+    }
+    fn detach_object(&self, inner: &mut ObjectInner<M>) {
+        let mut slots = self.slots.lock().unwrap();
+        slots.in_use -= 1;
         let add_permits = slots.size <= slots.max_size;
         slots.size -= 1;
         drop(slots);
         if add_permits {
             self.semaphore.add_permits(1);
         }
-        self.manager.detach(obj);
+        self.manager.detach(&mut inner.obj);
+        self.observers.detach(&inner.metrics);
+        self.notify_replenish();
+        self.status_changed();
+    }
+    /// Checks whether an object has been created or recycled within the
+    /// [`PoolConfig::recycle_interval`] or the [`Manager::recycle_interval()`]
+    /// if none is configured.
+    fn is_recently_recycled(&self, metrics: &Metrics) -> bool {
+        let recycle_interval = self
+            .config
+            .recycle_interval
+            .or_else(|| self.manager.recycle_interval());
+        match recycle_interval {
+            Some(recycle_interval) => {
+                metrics.recycled.unwrap_or(metrics.created).elapsed() < recycle_interval
+            }
+            None => false,
+        }
+    }
+    /// Checks whether an object has exceeded the configured
+    /// [`PoolConfig::max_lifetime`] or [`PoolConfig::idle_timeout`].
+    fn is_expired(&self, metrics: &Metrics) -> bool {
+        if let Some(max_lifetime) = self.config.max_lifetime {
+            if metrics.age() > max_lifetime {
+                return true;
+            }
+        }
+        if let Some(idle_timeout) = self.config.idle_timeout {
+            if metrics.last_used() > idle_timeout {
+                return true;
+            }
+        }
+        false
+    }
+    fn status(&self) -> Status {
+        let circuit = match &self.circuit_breaker {
+            Some(circuit_breaker) => circuit_breaker.state(),
+            None => CircuitState::Closed,
+        };
+        let slots = self.slots.lock().unwrap();
+        Status {
+            max_size: slots.max_size,
+            size: slots.size,
+            available: slots.vec.len(),
+            in_use: slots.in_use,
+            creating: slots.creating,
+            recycling: slots.recycling,
+            waiting: slots.waiting,
+            circuit,
+        }
+    }
+    /// Counts a timeout of the given type in the [`Stats`].
+    fn timeout(&self, timeout_type: TimeoutType) {
+        match timeout_type {
+            TimeoutType::Wait => self.stats.wait_timeout(),
+            TimeoutType::Create => self.stats.create_timeout(),
+            TimeoutType::Recycle => self.stats.recycle_timeout(),
+        }
+    }
+    /// Reports the current [`Status`] to the attached [`PoolObserver`]s.
+    ///
+    /// This must not be called while holding the `slots` lock.
+    fn status_changed(&self) {
+        if !self.observers.is_empty() {
+            self.observers.status(self.status());
+        }
+        if self.semaphore.is_closed() {
+            self.drained.notify_one();
+        }
+        if let Some(budget) = &self.budget {
+            budget.notify();
+        }
+    }
+    /// Wakes up the task maintaining [`PoolConfig::min_idle`] objects if
+    /// there is one.
+    fn notify_replenish(&self) {
+        if self.config.min_idle > 0 {
+            self.replenish.notify_one();
+        }
+    }
+}
+
+impl<M: Manager> budget::Member for PoolInner<M> {
+    fn least_recently_used(&self) -> Option<Duration> {
+        let slots = self.slots.lock().unwrap();
+        slots.vec.iter().map(|obj| obj.metrics.last_used()).max()
+    }
+
+    fn evict_idle(&self) -> bool {
+        let mut slots = self.slots.lock().unwrap();
+        let index = slots
+            .vec
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, obj)| obj.metrics.last_used())
+            .map(|(index, _)| index);
+        let mut inner = match index.and_then(|index| slots.vec.remove(index)) {
+            Some(inner) => inner,
+            None => return false,
+        };
+        slots.size -= 1;
+        drop(slots);
+        self.manager.detach(&mut inner.obj);
+        self.observers.detach(&inner.metrics);
+        self.stats.detached();
+        self.notify_replenish();
+        self.status_changed();
+        true
     }
 }
 
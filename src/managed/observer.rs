@@ -0,0 +1,132 @@
+//! Observers allowing to monitor events happening inside a [`Pool`].
+//!
+//! [`Pool`]: super::Pool
+
+use std::{fmt, time::Duration};
+
+use super::{
+    CheckoutInfo, HookError, HookType, Manager, Metrics, PoolError, RecycleError, Status,
+    TimeoutType,
+};
+
+/// Observer receiving events of a [`Pool`].
+///
+/// All methods have a default implementation which does nothing, so
+/// implementations only need to override the events they are interested in.
+/// The methods are called synchronously from within the [`Pool`] and should
+/// therefore return quickly and never block.
+///
+/// Observers are attached to a [`Pool`] using the
+/// [`PoolBuilder::observer()`] method.
+///
+/// [`Pool`]: super::Pool
+/// [`PoolBuilder::observer()`]: super::PoolBuilder::observer
+pub trait PoolObserver<M: Manager>: Sync + Send {
+    /// Called when a task has acquired a slot of the [`Pool`] after waiting
+    /// for the given `duration`.
+    ///
+    /// [`Pool`]: super::Pool
+    fn checkout_wait(&self, _duration: Duration) {}
+
+    /// Called after a new object has been created.
+    fn create_success(&self, _duration: Duration) {}
+
+    /// Called after creating a new object failed. This includes timeouts
+    /// while creating objects.
+    fn create_failure(&self, _duration: Duration, _error: &PoolError<M::Error>) {}
+
+    /// Called after an object has been recycled.
+    fn recycle_success(&self, _duration: Duration) {}
+
+    /// Called after [`Manager::recycle()`] returned an error.
+    fn recycle_failure(&self, _duration: Duration, _error: &RecycleError<M::Error>) {}
+
+    /// Called when a hook returned an error.
+    fn hook_failure(&self, _hook_type: HookType, _error: &HookError<M::Error>) {}
+
+    /// Called after an object has been detached from the [`Pool`] and the
+    /// [`Manager::detach()`] method has been called.
+    ///
+    /// [`Pool`]: super::Pool
+    fn detach(&self, _metrics: &Metrics) {}
+
+    /// Called when a timeout occurred.
+    fn timeout(&self, _timeout_type: TimeoutType) {}
+
+    /// Called once for every object which has been checked out for longer
+    /// than the [`PoolConfig::leak_detection_threshold`].
+    ///
+    /// [`PoolConfig::leak_detection_threshold`]: super::PoolConfig::leak_detection_threshold
+    fn leak_detected(&self, _info: &CheckoutInfo) {}
+
+    /// Called whenever the [`Status`] of the [`Pool`] might have changed,
+    /// e.g. when objects are created, handed out, returned or detached.
+    ///
+    /// [`Pool`]: super::Pool
+    fn status(&self, _status: Status) {}
+}
+
+pub(crate) struct Observers<M: Manager> {
+    vec: Vec<Box<dyn PoolObserver<M>>>,
+}
+
+// Implemented manually to avoid unnecessary trait bound on `M` type parameter.
+impl<M: Manager> fmt::Debug for Observers<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Observers")
+            .field("len", &self.vec.len())
+            .finish_non_exhaustive()
+    }
+}
+
+// Implemented manually to avoid unnecessary trait bound on `M` type parameter.
+impl<M: Manager> Default for Observers<M> {
+    fn default() -> Self {
+        Self { vec: Vec::new() }
+    }
+}
+
+impl<M: Manager> Observers<M> {
+    pub(crate) fn push(&mut self, observer: Box<dyn PoolObserver<M>>) {
+        self.vec.push(observer);
+    }
+    pub(crate) fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+    pub(crate) fn checkout_wait(&self, duration: Duration) {
+        self.vec.iter().for_each(|o| o.checkout_wait(duration));
+    }
+    pub(crate) fn create_success(&self, duration: Duration) {
+        self.vec.iter().for_each(|o| o.create_success(duration));
+    }
+    pub(crate) fn create_failure(&self, duration: Duration, error: &PoolError<M::Error>) {
+        self.vec
+            .iter()
+            .for_each(|o| o.create_failure(duration, error));
+    }
+    pub(crate) fn recycle_success(&self, duration: Duration) {
+        self.vec.iter().for_each(|o| o.recycle_success(duration));
+    }
+    pub(crate) fn recycle_failure(&self, duration: Duration, error: &RecycleError<M::Error>) {
+        self.vec
+            .iter()
+            .for_each(|o| o.recycle_failure(duration, error));
+    }
+    pub(crate) fn hook_failure(&self, hook_type: HookType, error: &HookError<M::Error>) {
+        self.vec
+            .iter()
+            .for_each(|o| o.hook_failure(hook_type, error));
+    }
+    pub(crate) fn detach(&self, metrics: &Metrics) {
+        self.vec.iter().for_each(|o| o.detach(metrics));
+    }
+    pub(crate) fn timeout(&self, timeout_type: TimeoutType) {
+        self.vec.iter().for_each(|o| o.timeout(timeout_type));
+    }
+    pub(crate) fn leak_detected(&self, info: &CheckoutInfo) {
+        self.vec.iter().for_each(|o| o.leak_detected(info));
+    }
+    pub(crate) fn status(&self, status: Status) {
+        self.vec.iter().for_each(|o| o.status(status));
+    }
+}
@@ -0,0 +1,105 @@
+//! Pausing and resuming a [`Pool`].
+//!
+//! [`Pool`]: super::Pool
+
+use std::sync::Mutex;
+
+use tokio::sync::{Notify, Semaphore};
+
+use super::{queue::WaitQueue, PoolError};
+
+/// Options for pausing a [`Pool`] using [`Pool::pause()`].
+///
+/// [`Pool`]: super::Pool
+/// [`Pool::pause()`]: super::Pool::pause
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PauseOptions {
+    /// Fail tasks retrieving objects with a [`PoolError::Paused`] instead
+    /// of letting them wait until the [`Pool`] is resumed.
+    ///
+    /// [`Pool`]: super::Pool
+    pub fail_fast: bool,
+
+    /// Detach all idle objects when pausing the [`Pool`] so new objects are
+    /// created after resuming it.
+    ///
+    /// [`Pool`]: super::Pool
+    pub drop_idle: bool,
+}
+
+#[derive(Debug)]
+pub(super) struct PauseState {
+    options: Mutex<Option<PauseOptions>>,
+    /// Wakes up the tasks waiting for the [`Pool`] to be resumed whenever
+    /// the state changes.
+    ///
+    /// [`Pool`]: super::Pool
+    changed: Notify,
+}
+
+impl PauseState {
+    pub(super) fn new() -> Self {
+        Self {
+            options: Mutex::new(None),
+            changed: Notify::new(),
+        }
+    }
+
+    pub(super) fn pause(&self, options: PauseOptions) {
+        *self.options.lock().unwrap() = Some(options);
+        self.changed.notify_waiters();
+    }
+
+    pub(super) fn resume(&self) {
+        *self.options.lock().unwrap() = None;
+        self.changed.notify_waiters();
+    }
+
+    /// Wakes up the tasks waiting for the [`Pool`] to be resumed after it
+    /// has been closed.
+    ///
+    /// [`Pool`]: super::Pool
+    pub(super) fn notify_closed(&self) {
+        self.changed.notify_waiters();
+    }
+
+    /// Returns the [`PauseOptions`] if the [`Pool`] is paused.
+    ///
+    /// [`Pool`]: super::Pool
+    pub(super) fn get(&self) -> Option<PauseOptions> {
+        *self.options.lock().unwrap()
+    }
+
+    /// Waits until the [`Pool`] isn't paused or fails if it has been paused
+    /// with [`PauseOptions::fail_fast`] or closed.
+    ///
+    /// Waiting tasks count towards the [`PoolConfig::max_waiting`] of the
+    /// `queue`.
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`PoolConfig::max_waiting`]: super::PoolConfig::max_waiting
+    pub(super) async fn wait_resumed<E>(
+        &self,
+        semaphore: &Semaphore,
+        queue: &WaitQueue,
+    ) -> Result<(), PoolError<E>> {
+        let mut parked = None;
+        loop {
+            // Created before checking the state so no change is missed.
+            let changed = self.changed.notified();
+            if semaphore.is_closed() {
+                return Err(PoolError::Closed);
+            }
+            match self.get() {
+                None => return Ok(()),
+                Some(options) if options.fail_fast => return Err(PoolError::Paused),
+                Some(_) => {
+                    if parked.is_none() {
+                        parked = Some(queue.park().map_err(|_| PoolError::QueueFull)?);
+                    }
+                    changed.await;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! Bounded and priority aware queue of tasks waiting for a slot of a
+//! [`Pool`].
+//!
+//! Only the task at the head of the queue waits on the [`Semaphore`] of the
+//! [`Pool`]. All other tasks wait until they become the head of the queue.
+//! Whenever a task with a higher priority is enqueued the current head is
+//! preempted and stops waiting on the [`Semaphore`].
+//!
+//! [`Pool`]: super::Pool
+
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use tokio::sync::{Notify, Semaphore, SemaphorePermit, TryAcquireError};
+
+use super::PoolConfig;
+
+/// Priority of a task retrieving an [`Object`] from a [`Pool`].
+///
+/// Tasks waiting for an [`Object`] are served by their [`Priority`] first and
+/// in FIFO order second.
+///
+/// [`Object`]: super::Object
+/// [`Pool`]: super::Pool
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Priority {
+    /// Lowest priority, e.g. for batch jobs.
+    Low,
+
+    /// Priority used by [`Pool::get()`] and [`Pool::timeout_get()`].
+    ///
+    /// [`Pool::get()`]: super::Pool::get
+    /// [`Pool::timeout_get()`]: super::Pool::timeout_get
+    Normal,
+
+    /// Highest priority, e.g. for latency critical requests.
+    ///
+    /// Only tasks with this priority may use the slots reserved via
+    /// [`PoolConfig::high_priority_reserve`].
+    ///
+    /// [`PoolConfig::high_priority_reserve`]: super::PoolConfig::high_priority_reserve
+    High,
+}
+
+// Implemented manually to provide a custom documentation.
+impl Default for Priority {
+    /// Returns [`Priority::Normal`].
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+type Key = (Reverse<Priority>, u64);
+
+#[derive(Debug, Default)]
+struct QueueState {
+    next_id: u64,
+    waiters: BTreeMap<Key, Arc<Notify>>,
+    /// Number of tasks waiting outside of the queue, see
+    /// [`WaitQueue::park()`].
+    parked: usize,
+}
+
+/// Possible errors of [`WaitQueue::acquire()`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum AcquireError {
+    /// The semaphore has been closed.
+    Closed,
+
+    /// [`PoolConfig::max_waiting`] tasks are already waiting.
+    QueueFull,
+}
+
+#[derive(Debug)]
+pub(crate) struct WaitQueue {
+    /// See [`PoolConfig::high_priority_reserve`].
+    reserve: usize,
+    /// See [`PoolConfig::max_waiting`].
+    max_waiting: Option<usize>,
+    state: Mutex<QueueState>,
+}
+
+impl WaitQueue {
+    pub(crate) fn new(config: &PoolConfig) -> Self {
+        Self {
+            reserve: config.high_priority_reserve,
+            max_waiting: config.max_waiting,
+            state: Mutex::new(QueueState::default()),
+        }
+    }
+
+    /// Tries to acquire `n` permits of the `semaphore` without waiting.
+    ///
+    /// Tasks which don't have the [`Priority::High`] leave the last
+    /// [`PoolConfig::high_priority_reserve`] permits untouched.
+    pub(crate) fn try_acquire<'a>(
+        &self,
+        semaphore: &'a Semaphore,
+        priority: Priority,
+        n: u32,
+    ) -> Result<SemaphorePermit<'a>, TryAcquireError> {
+        let permits = required_permits(priority, self.reserve, n);
+        if permits > n {
+            drop(semaphore.try_acquire_many(permits)?);
+        }
+        semaphore.try_acquire_many(n)
+    }
+
+    /// Acquires `n` permits of the `semaphore` at once after all tasks with
+    /// a higher [`Priority`] and all tasks with the same [`Priority`] which
+    /// have been enqueued earlier.
+    ///
+    /// Tasks which don't have the [`Priority::High`] leave the last
+    /// [`PoolConfig::high_priority_reserve`] permits untouched.
+    pub(crate) async fn acquire<'a>(
+        &self,
+        semaphore: &'a Semaphore,
+        priority: Priority,
+        n: u32,
+    ) -> Result<SemaphorePermit<'a>, AcquireError> {
+        if self.is_empty() {
+            match self.try_acquire(semaphore, priority, n) {
+                Ok(permit) => return Ok(permit),
+                Err(TryAcquireError::Closed) => return Err(AcquireError::Closed),
+                Err(TryAcquireError::NoPermits) => {}
+            }
+        }
+        let permits = required_permits(priority, self.reserve, n);
+        let waiter = self.enqueue(priority)?;
+        loop {
+            if !waiter.is_head() {
+                waiter.notify.notified().await;
+                continue;
+            }
+            let acquire = semaphore.acquire_many(permits);
+            let preempted = waiter.notify.notified();
+            tokio::pin!(acquire);
+            tokio::pin!(preempted);
+            let result = Preemptible {
+                acquire: acquire.as_mut(),
+                preempted: preempted.as_mut(),
+            }
+            .await;
+            match result {
+                Some(Ok(permit)) if permits == n => return Ok(permit),
+                Some(Ok(permit)) => {
+                    // Keep the `n` permits only. If another task managed to
+                    // take them in the meantime wait for the next ones.
+                    drop(permit);
+                    if let Ok(permit) = semaphore.try_acquire_many(n) {
+                        return Ok(permit);
+                    }
+                }
+                Some(Err(_)) => return Err(AcquireError::Closed),
+                None => {}
+            }
+        }
+    }
+
+    /// Registers a task which waits for something other than a permit, e.g.
+    /// for the [`Pool`] to be resumed, so it counts towards the
+    /// [`PoolConfig::max_waiting`] tasks until the returned [`Parked`] guard
+    /// is dropped.
+    ///
+    /// [`Pool`]: super::Pool
+    pub(crate) fn park(&self) -> Result<Parked<'_>, AcquireError> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(max_waiting) = self.max_waiting {
+            if state.waiters.len() + state.parked >= max_waiting {
+                return Err(AcquireError::QueueFull);
+            }
+        }
+        state.parked += 1;
+        Ok(Parked { queue: self })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.state.lock().unwrap().waiters.is_empty()
+    }
+
+    fn enqueue(&self, priority: Priority) -> Result<Waiter<'_>, AcquireError> {
+        let notify = Arc::new(Notify::new());
+        let mut state = self.state.lock().unwrap();
+        if let Some(max_waiting) = self.max_waiting {
+            if state.waiters.len() + state.parked >= max_waiting {
+                return Err(AcquireError::QueueFull);
+            }
+        }
+        let key = (Reverse(priority), state.next_id);
+        state.next_id += 1;
+        let head = state.waiters.iter().next().map(|(k, n)| (*k, n.clone()));
+        let _ = state.waiters.insert(key, notify.clone());
+        drop(state);
+        if let Some((head_key, head_notify)) = head {
+            if key < head_key {
+                head_notify.notify_one();
+            }
+        }
+        Ok(Waiter {
+            queue: self,
+            key,
+            notify,
+        })
+    }
+}
+
+/// Number of permits which must be available for a task with the given
+/// `priority` to acquire `n` permits.
+fn required_permits(priority: Priority, reserve: usize, n: u32) -> u32 {
+    match priority {
+        Priority::High => n,
+        Priority::Normal | Priority::Low => {
+            reserve.saturating_add(n as usize).min(u32::MAX as usize) as u32
+        }
+    }
+}
+
+/// Entry of a [`WaitQueue`] which is removed when being dropped.
+struct Waiter<'a> {
+    queue: &'a WaitQueue,
+    key: Key,
+    notify: Arc<Notify>,
+}
+
+impl Waiter<'_> {
+    fn is_head(&self) -> bool {
+        let state = self.queue.state.lock().unwrap();
+        state.waiters.keys().next() == Some(&self.key)
+    }
+}
+
+impl Drop for Waiter<'_> {
+    fn drop(&mut self) {
+        let mut state = self.queue.state.lock().unwrap();
+        let was_head = state.waiters.keys().next() == Some(&self.key);
+        let _ = state.waiters.remove(&self.key);
+        if was_head {
+            if let Some(notify) = state.waiters.values().next() {
+                notify.notify_one();
+            }
+        }
+    }
+}
+
+/// Task registered via [`WaitQueue::park()`] which is removed when being
+/// dropped.
+pub(crate) struct Parked<'a> {
+    queue: &'a WaitQueue,
+}
+
+impl Drop for Parked<'_> {
+    fn drop(&mut self) {
+        self.queue.state.lock().unwrap().parked -= 1;
+    }
+}
+
+/// Future resolving to `None` when the `preempted` future completes before
+/// a permit has been acquired.
+struct Preemptible<'a, A, P> {
+    acquire: Pin<&'a mut A>,
+    preempted: Pin<&'a mut P>,
+}
+
+impl<A: Future, P: Future> Future for Preemptible<'_, A, P> {
+    type Output = Option<A::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Poll::Ready(result) = self.acquire.as_mut().poll(cx) {
+            return Poll::Ready(Some(result));
+        }
+        match self.preempted.as_mut().poll(cx) {
+            Poll::Ready(_) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! Background task removing expired objects from a [`Pool`].
+//!
+//! Objects expire when exceeding [`PoolConfig::max_lifetime`] or
+//! [`PoolConfig::idle_timeout`].
+//!
+//! [`PoolConfig::max_lifetime`]: super::PoolConfig::max_lifetime
+//! [`PoolConfig::idle_timeout`]: super::PoolConfig::idle_timeout
+
+use std::{marker::PhantomData, sync::Weak, time::Duration};
+
+use deadpool_runtime::Runtime;
+
+use super::{Manager, Pool, PoolInner};
+
+/// Spawns the task reaping expired objects of the given [`Pool`] every
+/// `interval`.
+///
+/// The task only holds a [`Weak`] reference to the [`Pool`] while sleeping
+/// and stops as soon as the [`Pool`] is dropped or closed.
+pub(super) fn spawn<M: Manager + 'static>(
+    runtime: Runtime,
+    pool: Weak<PoolInner<M>>,
+    interval: Duration,
+) {
+    runtime.spawn(async move {
+        loop {
+            runtime.sleep(interval).await;
+            let inner = match pool.upgrade() {
+                Some(inner) => inner,
+                None => break,
+            };
+            if inner.semaphore.is_closed() {
+                break;
+            }
+            let pool = Pool::<M> {
+                inner,
+                _wrapper: PhantomData,
+            };
+            pool.retain(|_, metrics| !pool.inner.is_expired(&metrics));
+        }
+    });
+}
@@ -0,0 +1,114 @@
+//! Recycling of objects outside of [`Pool::get()`], see [`RecycleMode`].
+//!
+//! [`Pool::get()`]: super::Pool::get
+//! [`RecycleMode`]: super::RecycleMode
+
+use std::{
+    marker::PhantomData,
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+use deadpool_runtime::Runtime;
+
+use super::{Manager, ObjectInner, Pool, PoolInner, Stage, UnreadyObject};
+
+/// Function spawning the task recycling an object which has just been
+/// returned to its [`Pool`], see [`RecycleMode::OnReturn`].
+///
+/// The object is handed back if no task can be spawned on the current
+/// thread, e.g. when it's dropped outside of a Tokio runtime.
+///
+/// [`RecycleMode::OnReturn`]: super::RecycleMode::OnReturn
+pub(super) type RecycleOnReturn<M> =
+    Box<dyn Fn(Arc<PoolInner<M>>, ObjectInner<M>) -> Result<(), ObjectInner<M>> + Send + Sync>;
+
+/// Creates the [`RecycleOnReturn`] function of a [`Pool`].
+///
+/// Objects are returned to the [`Pool`] when being dropped which doesn't
+/// require `M: 'static`. Spawning tasks does, so this is captured here
+/// when building the [`Pool`].
+pub(super) fn recycle_on_return<M: Manager + 'static>(runtime: Runtime) -> RecycleOnReturn<M> {
+    Box::new(move |inner, obj| {
+        if !runtime.can_spawn() {
+            return Err(obj);
+        }
+        runtime.spawn(async move {
+            let pool = Pool::<M> {
+                inner,
+                _wrapper: PhantomData,
+            };
+            let unready_obj = UnreadyObject {
+                generation: obj.generation,
+                inner: Some(obj),
+                stage: Some(Stage::Returning),
+                pool: &pool.inner,
+            };
+            let result = pool.try_recycle(&pool.timeouts(), unready_obj).await;
+            // Objects failing to be recycled are detached by dropping them.
+            if let Ok(Some(unready_obj)) = result {
+                unready_obj.into_idle();
+            }
+        });
+        Ok(())
+    })
+}
+
+/// Spawns the task recycling the idle objects of the given [`Pool`] which
+/// have been used since they were last recycled every `interval`, see
+/// [`RecycleMode::Background`].
+///
+/// The task only holds a [`Weak`] reference to the [`Pool`] while sleeping
+/// and stops as soon as the [`Pool`] is dropped or closed.
+///
+/// [`RecycleMode::Background`]: super::RecycleMode::Background
+pub(super) fn spawn<M: Manager + 'static>(
+    runtime: Runtime,
+    pool: Weak<PoolInner<M>>,
+    interval: Duration,
+) {
+    runtime.spawn(async move {
+        loop {
+            runtime.sleep(interval).await;
+            let inner = match pool.upgrade() {
+                Some(inner) => inner,
+                None => break,
+            };
+            if inner.semaphore.is_closed() {
+                break;
+            }
+            recycle_idle(Pool {
+                inner,
+                _wrapper: PhantomData,
+            })
+            .await;
+        }
+    });
+}
+
+/// Recycles every idle object which has been used since it was last
+/// recycled.
+///
+/// Every object is recycled while holding a semaphore permit just like
+/// [`Pool::get()`] does. If there is no permit left all idle objects are
+/// about to be handed out and recycling them is skipped.
+///
+/// [`Pool::get()`]: super::Pool::get
+async fn recycle_idle<M: Manager>(pool: Pool<M>) {
+    let timeouts = pool.timeouts();
+    loop {
+        let permit = match pool.inner.semaphore.try_acquire() {
+            Ok(permit) => permit,
+            Err(_) => return,
+        };
+        let unready_obj = match pool.inner.pop_dirty() {
+            Some(unready_obj) => unready_obj,
+            None => return,
+        };
+        // Objects failing to be recycled are detached by dropping them.
+        if let Ok(Some(unready_obj)) = pool.try_recycle(&timeouts, unready_obj).await {
+            unready_obj.into_idle();
+        }
+        drop(permit);
+    }
+}
@@ -15,7 +15,10 @@
 //! ```
 
 pub use crate::{
-    managed::{Metrics, PoolConfig, Status, Timeouts},
+    managed::{
+        CircuitBreakerConfig, CircuitState, Metrics, PoolBudget, PoolConfig, Priority, Stats,
+        Status, Timeouts,
+    },
     Runtime,
 };
 
@@ -51,5 +54,8 @@ macro_rules! managed_reexports {
         #[doc=concat!("Type alias for using [`deadpool::managed::HookError`] with [`", $crate_name, "`].")]
         pub type HookError = deadpool::managed::HookError<$Error>;
 
+        #[doc=concat!("Type alias for using [`deadpool::managed::WarmUpReport`] with [`", $crate_name, "`].")]
+        pub type WarmUpReport = deadpool::managed::WarmUpReport<$Error>;
+
     };
 }
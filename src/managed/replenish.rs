@@ -0,0 +1,95 @@
+//! Background task keeping [`PoolConfig::min_idle`] objects in a [`Pool`].
+//!
+//! [`PoolConfig::min_idle`]: super::PoolConfig::min_idle
+
+use std::{
+    marker::PhantomData,
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+use deadpool_runtime::Runtime;
+use tokio::sync::Notify;
+
+use super::{Manager, Pool, PoolInner};
+
+/// Interval in which the task checks the [`Pool`] even if it wasn't notified.
+///
+/// This also serves as back-off after a failed attempt to create an object
+/// during which notifications are ignored.
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Spawns the task replenishing the given [`Pool`].
+///
+/// The task only holds a [`Weak`] reference to the [`Pool`] while waiting
+/// and stops as soon as the [`Pool`] is dropped or closed.
+pub(super) fn spawn<M: Manager + 'static>(
+    runtime: Runtime,
+    pool: Weak<PoolInner<M>>,
+    notify: Arc<Notify>,
+) {
+    runtime.spawn(async move {
+        loop {
+            let inner = match pool.upgrade() {
+                Some(inner) => inner,
+                None => break,
+            };
+            if inner.semaphore.is_closed() {
+                break;
+            }
+            let failed = replenish(Pool {
+                inner,
+                _wrapper: PhantomData,
+            })
+            .await;
+            if failed {
+                // Checkouts notify the task, so they must not cut the
+                // back-off short.
+                runtime.sleep(CHECK_INTERVAL).await;
+            } else {
+                let _ = runtime.timeout(CHECK_INTERVAL, notify.notified()).await;
+            }
+        }
+    });
+}
+
+/// Creates objects until the [`Pool`] holds at least
+/// [`PoolConfig::min_idle`] idle objects or its `max_size` has been reached.
+///
+/// Every object is created while holding a semaphore permit so the
+/// replenisher competes with regular [`Pool::get()`] calls just like any
+/// other user of the [`Pool`].
+///
+/// Returns `true` if creating an object failed.
+///
+/// [`PoolConfig::min_idle`]: super::PoolConfig::min_idle
+async fn replenish<M: Manager>(pool: Pool<M>) -> bool {
+    let timeouts = pool.timeouts();
+    loop {
+        if pool.inner.pause.get().is_some() {
+            return false;
+        }
+        let permit = match pool.inner.semaphore.try_acquire() {
+            Ok(permit) => permit,
+            Err(_) => return false,
+        };
+        // Idle objects of other pools aren't evicted just to keep idle
+        // objects around.
+        if let Some(budget) = &pool.inner.budget {
+            if budget.available() == 0 {
+                return false;
+            }
+        }
+        {
+            let slots = pool.inner.slots.lock().unwrap();
+            if slots.vec.len() >= pool.inner.config.min_idle || slots.size >= slots.max_size {
+                return false;
+            }
+        }
+        match pool.try_create(&timeouts).await {
+            Ok(unready_obj) => unready_obj.into_idle(),
+            Err(_) => return true,
+        }
+        drop(permit);
+    }
+}
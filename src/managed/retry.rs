@@ -0,0 +1,36 @@
+//! Backoff between attempts to create a new object.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use super::CreateRetryConfig;
+
+/// Returns the time to wait after the given failed `attempt` (starting at
+/// `1`) before trying again.
+pub(crate) fn backoff(config: &CreateRetryConfig, attempt: u32) -> Duration {
+    let backoff = 2u32
+        .checked_pow(attempt.saturating_sub(1))
+        .and_then(|factor| config.base_backoff.checked_mul(factor))
+        .map_or(config.max_backoff, |backoff| {
+            backoff.min(config.max_backoff)
+        });
+    if config.jitter {
+        // Random number in the range `[0, 1)`
+        let factor = (random() >> 11) as f64 / (1u64 << 53) as f64;
+        backoff / 2 + (backoff / 2).mul_f64(factor)
+    } else {
+        backoff
+    }
+}
+
+/// Returns a random number.
+///
+/// Every [`RandomState`] is seeded differently which is good enough for
+/// jitter and choosing random objects and avoids a dependency on a random
+/// number generator.
+pub(super) fn random() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
@@ -0,0 +1,138 @@
+//! Strategies for choosing the idle object of a [`Pool`] which is handed out
+//! next.
+//!
+//! [`Pool`]: super::Pool
+
+use std::{cmp::Reverse, collections::VecDeque, fmt};
+
+use super::{retry::random, Manager, Metrics, ObjectInner, QueueMode};
+
+/// Strategy for choosing the idle object of a [`Pool`] which is handed out
+/// next.
+///
+/// The built-in strategies are the variants of [`QueueMode`] which can be
+/// set via the [`PoolConfig::queue_mode`]. Custom strategies are attached
+/// using the [`PoolBuilder::object_selector()`] method and take precedence
+/// over the [`PoolConfig::queue_mode`]. Closures taking the [`IdleObjects`]
+/// and returning an index implement this trait as well.
+///
+/// [`Pool`]: super::Pool
+/// [`PoolBuilder::object_selector()`]: super::PoolBuilder::object_selector
+/// [`PoolConfig::queue_mode`]: super::PoolConfig::queue_mode
+pub trait ObjectSelector: Sync + Send {
+    /// Returns the index of the idle object to hand out next.
+    ///
+    /// This is only called if there is at least one idle object. If the
+    /// returned index is out of bounds the least recently returned object is
+    /// handed out instead. As this is called while the [`Pool`] is locked it
+    /// should return quickly and never block.
+    ///
+    /// [`Pool`]: super::Pool
+    fn select(&self, idle: &IdleObjects<'_>) -> usize;
+}
+
+impl<F> ObjectSelector for F
+where
+    F: Fn(&IdleObjects<'_>) -> usize + Sync + Send,
+{
+    fn select(&self, idle: &IdleObjects<'_>) -> usize {
+        self(idle)
+    }
+}
+
+impl ObjectSelector for QueueMode {
+    fn select(&self, idle: &IdleObjects<'_>) -> usize {
+        match self {
+            Self::Fifo => 0,
+            Self::Lifo => idle.len() - 1,
+            Self::LeastRecycled => idle.position_min_by_key(|metrics| metrics.recycle_count),
+            Self::Newest => idle.position_min_by_key(|metrics| Reverse(metrics.created)),
+            Self::Random => (random() % idle.len() as u64) as usize,
+        }
+    }
+}
+
+/// Idle objects of a [`Pool`] an [`ObjectSelector`] chooses from, the least
+/// recently returned one first.
+///
+/// [`Pool`]: super::Pool
+pub struct IdleObjects<'a> {
+    objs: &'a dyn Idle,
+}
+
+impl fmt::Debug for IdleObjects<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a> IdleObjects<'a> {
+    /// Returns the number of idle objects.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.objs.len()
+    }
+
+    /// Indicates whether there are no idle objects.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the [`Metrics`] of the idle object at the given `index`.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&'a Metrics> {
+        if index < self.len() {
+            Some(self.objs.metrics(index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the [`Metrics`] of all idle objects.
+    pub fn iter(&self) -> impl Iterator<Item = &'a Metrics> + 'a {
+        let objs = self.objs;
+        (0..objs.len()).map(move |index| objs.metrics(index))
+    }
+
+    /// Returns the index of the first idle object with the smallest key.
+    fn position_min_by_key<K: Ord>(&self, f: impl Fn(&Metrics) -> K) -> usize {
+        self.iter()
+            .enumerate()
+            .min_by_key(|(_, metrics)| f(metrics))
+            .map_or(0, |(index, _)| index)
+    }
+}
+
+/// Type-erased idle objects, so [`ObjectSelector`]s don't depend on the
+/// [`Manager`].
+trait Idle {
+    fn len(&self) -> usize;
+    fn metrics(&self, index: usize) -> &Metrics;
+}
+
+impl<M: Manager> Idle for VecDeque<ObjectInner<M>> {
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    fn metrics(&self, index: usize) -> &Metrics {
+        &self[index].metrics
+    }
+}
+
+/// Takes the idle object chosen by the given [`ObjectSelector`].
+pub(super) fn take<M: Manager>(
+    selector: &dyn ObjectSelector,
+    objs: &mut VecDeque<ObjectInner<M>>,
+) -> Option<ObjectInner<M>> {
+    if objs.is_empty() {
+        return None;
+    }
+    let index = selector.select(&IdleObjects { objs: &*objs });
+    if index < objs.len() {
+        objs.remove(index)
+    } else {
+        objs.pop_front()
+    }
+}
@@ -0,0 +1,167 @@
+//! Objects lent to multiple users concurrently, see [`Pool::get_shared()`].
+//!
+//! [`Pool::get_shared()`]: super::Pool::get_shared
+
+use std::{
+    any::Any,
+    fmt,
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
+
+use super::{Manager, Object};
+
+/// Handle to an [`Object`] which is lent to multiple users concurrently.
+///
+/// Instances of this are created by calling the [`Pool::get_shared()`]
+/// method. The [`Object`] is returned to its [`Pool`] once the handles of
+/// all its users have been dropped.
+///
+/// [`Pool`]: super::Pool
+/// [`Pool::get_shared()`]: super::Pool::get_shared
+#[must_use]
+pub struct SharedObject<M: Manager> {
+    obj: Arc<Object<M>>,
+}
+
+impl<M> fmt::Debug for SharedObject<M>
+where
+    M: fmt::Debug + Manager,
+    M::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedObject")
+            .field("obj", &self.obj)
+            .finish()
+    }
+}
+
+impl<M: Manager> SharedObject<M> {
+    /// Returns the underlying [`Object`].
+    pub fn object(this: &Self) -> &Object<M> {
+        &this.obj
+    }
+}
+
+impl<M: Manager> Drop for SharedObject<M> {
+    fn drop(&mut self) {
+        if let Some(pool) = self.obj.pool.upgrade() {
+            pool.shared.release(Arc::as_ptr(&self.obj) as *const ());
+        }
+    }
+}
+
+impl<M: Manager> Deref for SharedObject<M> {
+    type Target = M::Type;
+    fn deref(&self) -> &M::Type {
+        &self.obj
+    }
+}
+
+impl<M: Manager> AsRef<M::Type> for SharedObject<M> {
+    fn as_ref(&self) -> &M::Type {
+        self
+    }
+}
+
+struct Slot {
+    /// The [`Object`] which is type-erased so [`SharedObjects`] doesn't
+    /// require [`Manager::Type`] to be [`Sync`] for all [`Pool`]s.
+    ///
+    /// [`Pool`]: super::Pool
+    obj: Arc<dyn Any + Send + Sync>,
+    users: usize,
+}
+
+/// [`Object`]s of a [`Pool`] which are currently lent to users via
+/// [`Pool::get_shared()`].
+///
+/// [`Pool`]: super::Pool
+/// [`Pool::get_shared()`]: super::Pool::get_shared
+pub(super) struct SharedObjects {
+    slots: Mutex<Vec<Slot>>,
+}
+
+impl fmt::Debug for SharedObjects {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedObjects")
+            .field("len", &self.slots.lock().unwrap().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SharedObjects {
+    pub(super) fn new() -> Self {
+        Self {
+            slots: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Lends the object with the least users to another user unless all
+    /// objects already have `max_users` users.
+    ///
+    /// Objects of a previous `generation` of the [`Pool`] are removed instead
+    /// of being lent, so they are detached once their current users are
+    /// done. See [`Pool::invalidate_all()`].
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`Pool::invalidate_all()`]: super::Pool::invalidate_all
+    pub(super) fn lend<M>(&self, max_users: usize, generation: u64) -> Option<SharedObject<M>>
+    where
+        M: Manager + 'static,
+        M::Type: Sync,
+    {
+        let mut slots = self.slots.lock().unwrap();
+        slots.retain(|slot| {
+            slot.obj
+                .downcast_ref::<Object<M>>()
+                .and_then(|obj| obj.inner.as_ref())
+                .map(|inner| inner.generation)
+                == Some(generation)
+        });
+        let slot = slots
+            .iter_mut()
+            .filter(|slot| slot.users < max_users)
+            .min_by_key(|slot| slot.users)?;
+        let obj = slot.obj.clone().downcast::<Object<M>>().ok()?;
+        slot.users += 1;
+        Some(SharedObject { obj })
+    }
+
+    /// Starts lending the given object which has just been checked out of
+    /// the [`Pool`].
+    ///
+    /// [`Pool`]: super::Pool
+    pub(super) fn share<M>(&self, obj: Object<M>) -> SharedObject<M>
+    where
+        M: Manager + 'static,
+        M::Type: Sync,
+    {
+        let obj = Arc::new(obj);
+        self.slots.lock().unwrap().push(Slot {
+            obj: obj.clone(),
+            users: 1,
+        });
+        SharedObject { obj }
+    }
+
+    /// Releases one user of the object at the given address. The object is
+    /// removed once it has no users anymore so it's returned to the [`Pool`]
+    /// as soon as the last handle is dropped.
+    ///
+    /// [`Pool`]: super::Pool
+    fn release(&self, obj: *const ()) {
+        let mut slots = self.slots.lock().unwrap();
+        let index = match slots
+            .iter()
+            .position(|slot| Arc::as_ptr(&slot.obj) as *const () == obj)
+        {
+            Some(index) => index,
+            None => return,
+        };
+        slots[index].users -= 1;
+        if slots[index].users == 0 {
+            let _ = slots.swap_remove(index);
+        }
+    }
+}
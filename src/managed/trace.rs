@@ -0,0 +1,49 @@
+//! Helpers for the `tracing` instrumentation of the [`Pool`].
+//!
+//! [`Manager::Error`] isn't required to implement [`std::fmt::Display`], so
+//! backend errors can only be described by their kind. The actual errors
+//! are passed to [`PoolObserver`]s.
+//!
+//! [`Manager::Error`]: super::Manager::Error
+//! [`Pool`]: super::Pool
+//! [`PoolObserver`]: super::PoolObserver
+
+use super::{HookError, PoolError, RecycleError, TimeoutType};
+
+/// Returns the value of the `outcome` field of the `timeout_get` span.
+pub(super) fn outcome<T, E>(result: &Result<T, PoolError<E>>) -> &'static str {
+    match result {
+        Ok(_) => "ok",
+        Err(PoolError::Timeout(TimeoutType::Wait)) => "timeout_wait",
+        Err(PoolError::Timeout(TimeoutType::Create)) => "timeout_create",
+        Err(PoolError::Timeout(TimeoutType::Recycle)) => "timeout_recycle",
+        Err(PoolError::Backend(_)) => "backend_error",
+        Err(PoolError::Closed) => "closed",
+        Err(PoolError::NoRuntimeSpecified) => "no_runtime_specified",
+        Err(PoolError::PostCreateHook(_)) => "post_create_hook_error",
+        Err(PoolError::QueueFull) => "queue_full",
+        Err(PoolError::CircuitOpen) => "circuit_open",
+        Err(PoolError::Paused) => "paused",
+        Err(PoolError::TooManyObjects) => "too_many_objects",
+    }
+}
+
+/// Describes a [`RecycleError`] without requiring `E` to implement
+/// [`std::fmt::Display`].
+pub(super) fn recycle_error<E>(error: &RecycleError<E>) -> &str {
+    match error {
+        RecycleError::Message(msg) => msg,
+        RecycleError::StaticMessage(msg) => msg,
+        RecycleError::Backend(_) => "Backend error",
+    }
+}
+
+/// Describes a [`HookError`] without requiring `E` to implement
+/// [`std::fmt::Display`].
+pub(super) fn hook_error<E>(error: &HookError<E>) -> &str {
+    match error {
+        HookError::Message(msg) => msg,
+        HookError::StaticMessage(msg) => msg,
+        HookError::Backend(_) => "Backend error",
+    }
+}
@@ -0,0 +1,33 @@
+use super::PoolError;
+
+/// Report returned by [`Pool::warm_up()`].
+///
+/// [`Pool::warm_up()`]: super::Pool::warm_up
+#[derive(Debug)]
+pub struct WarmUpReport<E> {
+    /// Number of objects which were created and added to the [`Pool`].
+    ///
+    /// [`Pool`]: super::Pool
+    pub created: usize,
+
+    /// Errors of the creation attempts which failed.
+    pub errors: Vec<PoolError<E>>,
+}
+
+impl<E> WarmUpReport<E> {
+    /// Indicates whether all creation attempts succeeded.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+// Implemented manually to avoid unnecessary trait bound on `E` type parameter.
+impl<E> Default for WarmUpReport<E> {
+    fn default() -> Self {
+        Self {
+            created: 0,
+            errors: Vec::new(),
+        }
+    }
+}
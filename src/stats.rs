@@ -0,0 +1,80 @@
+//! Counters backing the [`Stats`] of the pools.
+
+use std::{
+    convert::TryFrom,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use crate::Stats;
+
+/// Monotonically increasing counters of a pool.
+///
+/// The counters are updated independently of each other, so a [`Stats`]
+/// snapshot isn't guaranteed to be consistent while the pool is being used.
+#[derive(Debug, Default)]
+pub(crate) struct StatsCounters {
+    created: AtomicU64,
+    create_errors: AtomicU64,
+    recycled: AtomicU64,
+    recycle_errors: AtomicU64,
+    detached: AtomicU64,
+    taken: AtomicU64,
+    wait_timeouts: AtomicU64,
+    create_timeouts: AtomicU64,
+    recycle_timeouts: AtomicU64,
+    wait_time_nanos: AtomicU64,
+    max_wait_time_nanos: AtomicU64,
+}
+
+// Most counters are only used by the managed pool.
+#[allow(dead_code)]
+impl StatsCounters {
+    pub(crate) fn created(&self) {
+        let _ = self.created.fetch_add(1, Ordering::Relaxed);
+    }
+    pub(crate) fn create_error(&self) {
+        let _ = self.create_errors.fetch_add(1, Ordering::Relaxed);
+    }
+    pub(crate) fn recycled(&self) {
+        let _ = self.recycled.fetch_add(1, Ordering::Relaxed);
+    }
+    pub(crate) fn recycle_error(&self) {
+        let _ = self.recycle_errors.fetch_add(1, Ordering::Relaxed);
+    }
+    pub(crate) fn detached(&self) {
+        let _ = self.detached.fetch_add(1, Ordering::Relaxed);
+    }
+    pub(crate) fn taken(&self) {
+        let _ = self.taken.fetch_add(1, Ordering::Relaxed);
+    }
+    pub(crate) fn wait_timeout(&self) {
+        let _ = self.wait_timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+    pub(crate) fn create_timeout(&self) {
+        let _ = self.create_timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+    pub(crate) fn recycle_timeout(&self) {
+        let _ = self.recycle_timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+    pub(crate) fn waited(&self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        let _ = self.wait_time_nanos.fetch_add(nanos, Ordering::Relaxed);
+        let _ = self.max_wait_time_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+    pub(crate) fn snapshot(&self) -> Stats {
+        Stats {
+            created: self.created.load(Ordering::Relaxed),
+            create_errors: self.create_errors.load(Ordering::Relaxed),
+            recycled: self.recycled.load(Ordering::Relaxed),
+            recycle_errors: self.recycle_errors.load(Ordering::Relaxed),
+            detached: self.detached.load(Ordering::Relaxed),
+            taken: self.taken.load(Ordering::Relaxed),
+            wait_timeouts: self.wait_timeouts.load(Ordering::Relaxed),
+            create_timeouts: self.create_timeouts.load(Ordering::Relaxed),
+            recycle_timeouts: self.recycle_timeouts.load(Ordering::Relaxed),
+            wait_time: Duration::from_nanos(self.wait_time_nanos.load(Ordering::Relaxed)),
+            max_wait_time: Duration::from_nanos(self.max_wait_time_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
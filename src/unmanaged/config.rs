@@ -17,6 +17,16 @@ pub struct PoolConfig {
     /// [`Runtime`] to be used.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub runtime: Option<Runtime>,
+
+    /// Maximum number of tasks waiting for an object.
+    ///
+    /// Once this number of tasks is waiting, [`Pool::get()`] fails
+    /// immediately with a [`PoolError::QueueFull`] instead of waiting.
+    ///
+    /// [`Pool::get()`]: super::Pool::get
+    /// [`PoolError::QueueFull`]: super::PoolError::QueueFull
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_waiting: Option<usize>,
 }
 
 impl PoolConfig {
@@ -27,6 +37,7 @@ impl PoolConfig {
             max_size,
             timeout: None,
             runtime: None,
+            max_waiting: None,
         }
     }
 }
@@ -13,6 +13,18 @@ pub enum PoolError {
 
     /// No runtime specified.
     NoRuntimeSpecified,
+
+    /// [`PoolConfig::max_waiting`] tasks are already waiting for an object.
+    ///
+    /// [`PoolConfig::max_waiting`]: super::PoolConfig::max_waiting
+    QueueFull,
+
+    /// More objects were requested at once than the [`Pool`] can ever hold,
+    /// see [`Pool::get_many()`].
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`Pool::get_many()`]: super::Pool::get_many
+    TooManyObjects,
 }
 
 impl fmt::Display for PoolError {
@@ -24,6 +36,8 @@ impl fmt::Display for PoolError {
             ),
             Self::Closed => write!(f, "Pool has been closed"),
             Self::NoRuntimeSpecified => write!(f, "No runtime specified"),
+            Self::QueueFull => write!(f, "Too many tasks waiting for an object"),
+            Self::TooManyObjects => write!(f, "Too many objects requested at once"),
         }
     }
 }
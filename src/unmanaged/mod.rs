@@ -32,18 +32,18 @@ mod config;
 mod errors;
 
 use std::{
-    convert::TryInto,
     ops::{Deref, DerefMut},
     sync::{
-        atomic::{AtomicIsize, AtomicUsize, Ordering},
+        atomic::{AtomicUsize, Ordering},
         Arc, Mutex, Weak,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use tokio::sync::{Semaphore, TryAcquireError};
+use tokio::sync::{Semaphore, SemaphorePermit, TryAcquireError};
 
-pub use crate::Status;
+use crate::stats::StatsCounters;
+pub use crate::{CircuitState, Stats, Status};
 
 pub use self::{config::PoolConfig, errors::PoolError};
 
@@ -69,8 +69,12 @@ impl<T> Object<T> {
     #[must_use]
     pub fn take(mut this: Self) -> T {
         if let Some(pool) = this.pool.upgrade() {
-            let _ = pool.size.fetch_sub(1, Ordering::Relaxed);
+            {
+                let _queue = pool.queue.lock().unwrap();
+                let _ = pool.size.fetch_sub(1, Ordering::Relaxed);
+            }
             pool.size_semaphore.add_permits(1);
+            pool.stats.taken();
         }
         this.obj.take().unwrap()
     }
@@ -84,7 +88,6 @@ impl<T> Drop for Object<T> {
                     let mut queue = pool.queue.lock().unwrap();
                     queue.push(obj);
                 }
-                let _ = pool.available.fetch_add(1, Ordering::Relaxed);
                 pool.semaphore.add_permits(1);
                 pool.clean_up();
             }
@@ -164,8 +167,9 @@ impl<T> Pool<T> {
                 queue: Mutex::new(Vec::with_capacity(config.max_size)),
                 size: AtomicUsize::new(0),
                 size_semaphore: Semaphore::new(config.max_size),
-                available: AtomicIsize::new(0),
                 semaphore: Semaphore::new(0),
+                waiting: AtomicUsize::new(0),
+                stats: StatsCounters::default(),
             }),
         }
     }
@@ -189,16 +193,17 @@ impl<T> Pool<T> {
     /// See [`PoolError`] for details.
     pub fn try_get(&self) -> Result<Object<T>, PoolError> {
         let inner = self.inner.as_ref();
+        let wait_start = Instant::now();
         let permit = inner.semaphore.try_acquire().map_err(|e| match e {
             TryAcquireError::NoPermits => PoolError::Timeout,
             TryAcquireError::Closed => PoolError::Closed,
-        })?;
+        });
+        let permit = inner.record_wait(wait_start, permit)?;
         let obj = {
             let mut queue = inner.queue.lock().unwrap();
             queue.pop().unwrap()
         };
         permit.forget();
-        let _ = inner.available.fetch_sub(1, Ordering::Relaxed);
         Ok(Object {
             pool: Arc::downgrade(&self.inner),
             obj: Some(obj),
@@ -212,38 +217,71 @@ impl<T> Pool<T> {
     ///
     /// See [`PoolError`] for details.
     pub async fn timeout_get(&self, timeout: Option<Duration>) -> Result<Object<T>, PoolError> {
-        let inner = self.inner.as_ref();
-        let permit = match (timeout, inner.config.runtime) {
-            (None, _) => inner
-                .semaphore
-                .acquire()
-                .await
-                .map_err(|_| PoolError::Closed),
-            (Some(timeout), _) if timeout.as_nanos() == 0 => {
-                inner.semaphore.try_acquire().map_err(|e| match e {
-                    TryAcquireError::NoPermits => PoolError::Timeout,
-                    TryAcquireError::Closed => PoolError::Closed,
-                })
-            }
-            (Some(timeout), Some(runtime)) => runtime
-                .timeout(timeout, inner.semaphore.acquire())
-                .await
-                .ok_or(PoolError::Timeout)?
-                .map_err(|_| PoolError::Closed),
-            (Some(_), None) => Err(PoolError::NoRuntimeSpecified),
-        }?;
+        let permit = self.inner.timeout_acquire(timeout, 1).await?;
         let obj = {
-            let mut queue = inner.queue.lock().unwrap();
+            let mut queue = self.inner.queue.lock().unwrap();
             queue.pop().unwrap()
         };
         permit.forget();
-        let _ = inner.available.fetch_sub(1, Ordering::Relaxed);
         Ok(Object {
             pool: Arc::downgrade(&self.inner),
             obj: Some(obj),
         })
     }
 
+    /// Retrieves `n` [`Object`]s from this [`Pool`] at once or waits for
+    /// them to become available.
+    ///
+    /// Either all `n` [`Object`]s are retrieved in a single operation or none
+    /// of them, so tasks needing multiple [`Object`]s at the same time can't
+    /// deadlock each other by holding some of them while waiting for the
+    /// rest.
+    ///
+    /// # Errors
+    ///
+    /// If `n` exceeds the [`PoolConfig::max_size`] a
+    /// [`PoolError::TooManyObjects`] is returned immediately. See
+    /// [`PoolError`] for other errors.
+    pub async fn get_many(&self, n: usize) -> Result<Vec<Object<T>>, PoolError> {
+        self.timeout_get_many(n, self.inner.config.timeout).await
+    }
+
+    /// Retrieves `n` [`Object`]s from this [`Pool`] at once using a different
+    /// `timeout` than the configured one. See [`Pool::get_many()`] for
+    /// details.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn timeout_get_many(
+        &self,
+        n: usize,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<Object<T>>, PoolError> {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        if n > self.inner.config.max_size {
+            return Err(PoolError::TooManyObjects);
+        }
+        let permits = n.min(u32::MAX as usize) as u32;
+        let permit = self.inner.timeout_acquire(timeout, permits).await?;
+        let objs = {
+            let mut queue = self.inner.queue.lock().unwrap();
+            let at = queue.len() - n;
+            queue.split_off(at)
+        };
+        permit.forget();
+        Ok(objs
+            .into_iter()
+            .rev()
+            .map(|obj| Object {
+                pool: Arc::downgrade(&self.inner),
+                obj: Some(obj),
+            })
+            .collect())
+    }
+
     /// Adds an `object` to this [`Pool`].
     ///
     /// If the [`Pool`] size has already reached its maximum, then this function
@@ -291,12 +329,11 @@ impl<T> Pool<T> {
     /// `max_size`. In the methods `add` and `try_add` this is ensured by using
     /// the `size_semaphore`.
     fn _add(&self, object: T) {
-        let _ = self.inner.size.fetch_add(1, Ordering::Relaxed);
         {
             let mut queue = self.inner.queue.lock().unwrap();
+            let _ = self.inner.size.fetch_add(1, Ordering::Relaxed);
             queue.push(object);
         }
-        let _ = self.inner.available.fetch_add(1, Ordering::Relaxed);
         self.inner.semaphore.add_permits(1);
     }
 
@@ -335,19 +372,27 @@ impl<T> Pool<T> {
     #[must_use]
     pub fn status(&self) -> Status {
         let max_size = self.inner.config.max_size;
+        let waiting = self.inner.waiting.load(Ordering::Relaxed);
+        // `size` is only modified while holding the lock of the `queue`.
+        let queue = self.inner.queue.lock().unwrap();
         let size = self.inner.size.load(Ordering::Relaxed);
-        let available = self.inner.available.load(Ordering::Relaxed);
         Status {
             max_size,
             size,
-            available: if available > 0 { available as usize } else { 0 },
-            waiting: if available < 0 {
-                (-available) as usize
-            } else {
-                0
-            },
+            available: queue.len(),
+            in_use: size - queue.len(),
+            creating: 0,
+            recycling: 0,
+            waiting,
+            circuit: CircuitState::Closed,
         }
     }
+
+    /// Retrieves the cumulative [`Stats`] of this [`Pool`].
+    #[must_use]
+    pub fn stats(&self) -> Stats {
+        self.inner.stats.snapshot()
+    }
 }
 
 #[derive(Debug)]
@@ -360,13 +405,12 @@ struct PoolInner<T> {
     /// semaphore and every time an [`Object`] is removed a permit is returned
     /// back.
     size_semaphore: Semaphore,
-    /// Number of available [`Object`]s in the [`Pool`]. If there are no
-    /// [`Object`]s in the [`Pool`] this number can become negative and store
-    /// the number of [`Future`]s waiting for an [`Object`].
+    semaphore: Semaphore,
+    /// Number of [`Future`]s waiting for a permit of the `semaphore`.
     ///
     /// [`Future`]: std::future::Future
-    available: AtomicIsize,
-    semaphore: Semaphore,
+    waiting: AtomicUsize,
+    stats: StatsCounters,
 }
 
 impl<T> PoolInner<T> {
@@ -385,12 +429,77 @@ impl<T> PoolInner<T> {
     fn clear(&self) {
         let mut queue = self.queue.lock().unwrap();
         let _ = self.size.fetch_sub(queue.len(), Ordering::Relaxed);
-        let _ = self
-            .available
-            .fetch_sub(queue.len() as isize, Ordering::Relaxed);
         queue.clear();
     }
 
+    /// Acquires `n` permits of the `semaphore` using the given `timeout` and
+    /// records the outcome in the [`Stats`].
+    async fn timeout_acquire(
+        &self,
+        timeout: Option<Duration>,
+        n: u32,
+    ) -> Result<SemaphorePermit<'_>, PoolError> {
+        let wait_start = Instant::now();
+        let permit = match (timeout, self.config.runtime) {
+            (None, _) => self.acquire(n).await,
+            (Some(timeout), _) if timeout.as_nanos() == 0 => {
+                self.semaphore.try_acquire_many(n).map_err(|e| match e {
+                    TryAcquireError::NoPermits => PoolError::Timeout,
+                    TryAcquireError::Closed => PoolError::Closed,
+                })
+            }
+            (Some(timeout), Some(runtime)) => runtime
+                .timeout(timeout, self.acquire(n))
+                .await
+                .unwrap_or(Err(PoolError::Timeout)),
+            (Some(_), None) => Err(PoolError::NoRuntimeSpecified),
+        };
+        self.record_wait(wait_start, permit)
+    }
+
+    /// Records the outcome of waiting for permits of the `semaphore` since
+    /// `wait_start` in the [`Stats`].
+    fn record_wait<'a>(
+        &self,
+        wait_start: Instant,
+        permit: Result<SemaphorePermit<'a>, PoolError>,
+    ) -> Result<SemaphorePermit<'a>, PoolError> {
+        match permit {
+            Ok(permit) => {
+                self.stats.waited(wait_start.elapsed());
+                Ok(permit)
+            }
+            Err(e) => {
+                if let PoolError::Timeout = e {
+                    self.stats.wait_timeout();
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Acquires `n` permits of the `semaphore` at once or waits for them to
+    /// become available unless [`PoolConfig::max_waiting`] tasks are already
+    /// waiting.
+    async fn acquire(&self, n: u32) -> Result<SemaphorePermit<'_>, PoolError> {
+        match self.semaphore.try_acquire_many(n) {
+            Ok(permit) => return Ok(permit),
+            Err(TryAcquireError::Closed) => return Err(PoolError::Closed),
+            Err(TryAcquireError::NoPermits) => {}
+        }
+        let waiting = self.waiting.fetch_add(1, Ordering::Relaxed);
+        let _guard = WaitingGuard(&self.waiting);
+        if let Some(max_waiting) = self.config.max_waiting {
+            if waiting >= max_waiting {
+                return Err(PoolError::QueueFull);
+            }
+        }
+        self.semaphore
+            .acquire_many(n)
+            .await
+            .map_err(|_| PoolError::Closed)
+    }
+
     /// Indicates whether this [`Pool`] has been closed.
     fn is_closed(&self) -> bool {
         matches!(
@@ -400,6 +509,15 @@ impl<T> PoolInner<T> {
     }
 }
 
+/// Decrements the number of waiting tasks when being dropped.
+struct WaitingGuard<'a>(&'a AtomicUsize);
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        let _ = self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 impl<T, I> From<I> for Pool<T>
 where
     I: IntoIterator<Item = T>,
@@ -416,8 +534,9 @@ where
                 config: PoolConfig::new(len),
                 size: AtomicUsize::new(len),
                 size_semaphore: Semaphore::new(0),
-                available: AtomicIsize::new(len.try_into().unwrap()),
                 semaphore: Semaphore::new(len),
+                waiting: AtomicUsize::new(0),
+                stats: StatsCounters::default(),
             }),
         }
     }
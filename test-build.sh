@@ -10,6 +10,8 @@ cargo build --no-default-features --features serde
 cargo build --no-default-features --features managed,serde
 cargo build --no-default-features --features unmanaged,serde
 cargo build --no-default-features --features managed,unmanaged
+cargo build --no-default-features --features managed,metrics
+cargo build --no-default-features --features managed,tracing
 
 (
 	cd postgres
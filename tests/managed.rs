@@ -1,9 +1,13 @@
 #![cfg(feature = "managed")]
 
-use std::{convert::Infallible, time::Duration};
+use std::{
+    convert::Infallible,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
 
 use async_trait::async_trait;
-use tokio::time;
+use tokio::{sync::Notify, time};
 
 use deadpool::managed::{self, Metrics, Object, PoolError, RecycleResult, Timeouts};
 
@@ -33,42 +37,49 @@ async fn basic() {
     let status = pool.status();
     assert_eq!(status.size, 0);
     assert_eq!(status.available, 0);
+    assert_eq!(status.in_use, 0);
     assert_eq!(status.waiting, 0);
 
     let obj0 = pool.get().await.unwrap();
     let status = pool.status();
     assert_eq!(status.size, 1);
     assert_eq!(status.available, 0);
+    assert_eq!(status.in_use, 1);
     assert_eq!(status.waiting, 0);
 
     let obj1 = pool.get().await.unwrap();
     let status = pool.status();
     assert_eq!(status.size, 2);
     assert_eq!(status.available, 0);
+    assert_eq!(status.in_use, 2);
     assert_eq!(status.waiting, 0);
 
     let obj2 = pool.get().await.unwrap();
     let status = pool.status();
     assert_eq!(status.size, 3);
     assert_eq!(status.available, 0);
+    assert_eq!(status.in_use, 3);
     assert_eq!(status.waiting, 0);
 
     drop(obj0);
     let status = pool.status();
     assert_eq!(status.size, 3);
     assert_eq!(status.available, 1);
+    assert_eq!(status.in_use, 2);
     assert_eq!(status.waiting, 0);
 
     drop(obj1);
     let status = pool.status();
     assert_eq!(status.size, 3);
     assert_eq!(status.available, 2);
+    assert_eq!(status.in_use, 1);
     assert_eq!(status.waiting, 0);
 
     drop(obj2);
     let status = pool.status();
     assert_eq!(status.size, 3);
     assert_eq!(status.available, 3);
+    assert_eq!(status.in_use, 0);
     assert_eq!(status.waiting, 0);
 }
 
@@ -195,6 +206,102 @@ async fn object_take() {
     assert_eq!(status.waiting, 0);
 }
 
+#[tokio::test]
+async fn object_mark_broken() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    let mut obj = pool.get().await.unwrap();
+    assert!(!Object::is_broken(&obj));
+    Object::mark_broken(&mut obj);
+    assert!(Object::is_broken(&obj));
+
+    drop(obj);
+    let status = pool.status();
+    assert_eq!(status.size, 0);
+    assert_eq!(status.available, 0);
+    assert_eq!(pool.stats().detached, 1);
+
+    // The slot has been freed for a new object
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::metrics(&obj).recycle_count, 0);
+    drop(obj);
+    assert_eq!(pool.status().available, 1);
+}
+
+#[tokio::test]
+async fn invalidate_all() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr).max_size(2).build().unwrap();
+    let obj0 = pool.get().await.unwrap();
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.status().size, 2);
+
+    pool.invalidate_all();
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 0);
+    assert_eq!(status.in_use, 1);
+
+    drop(obj0);
+    let status = pool.status();
+    assert_eq!(status.size, 0);
+    assert_eq!(status.available, 0);
+    assert_eq!(pool.stats().detached, 2);
+
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::metrics(&obj).recycle_count, 0);
+    drop(obj);
+    assert_eq!(pool.status().available, 1);
+}
+
+struct GatedManager {
+    created: AtomicUsize,
+    gate: Notify,
+}
+
+#[async_trait]
+impl managed::Manager for GatedManager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        let n = self.created.fetch_add(1, Ordering::Relaxed);
+        if n == 0 {
+            self.gate.notified().await;
+        }
+        Ok(n)
+    }
+
+    async fn recycle(&self, _conn: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn invalidate_all_creating() {
+    let mgr = GatedManager {
+        created: AtomicUsize::new(0),
+        gate: Notify::new(),
+    };
+    let pool: managed::Pool<GatedManager> =
+        managed::Pool::builder(mgr).max_size(1).build().unwrap();
+    let handle = tokio::spawn({
+        let pool = pool.clone();
+        async move { *pool.get().await.unwrap() }
+    });
+    while pool.status().size == 0 {
+        time::sleep(Duration::from_millis(1)).await;
+    }
+
+    pool.invalidate_all();
+    pool.manager().gate.notify_one();
+    assert_eq!(handle.await.unwrap(), 1);
+    assert_eq!(pool.stats().detached, 1);
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 1);
+}
+
 #[tokio::test]
 async fn resize_pool_shrink() {
     let mgr = Manager {};
@@ -299,3 +406,25 @@ async fn retain() {
     pool.retain(|_, metrics| metrics.age() <= Duration::from_millis(10));
     assert_eq!(pool.status().size, 0);
 }
+
+#[tokio::test]
+async fn max_waiting() {
+    let mgr = Manager {};
+    let pool = Pool::builder(mgr)
+        .max_size(1)
+        .max_waiting(Some(1))
+        .build()
+        .unwrap();
+
+    let obj = pool.get().await.unwrap();
+    let waiting = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get().await.map(drop) })
+    };
+    tokio::task::yield_now().await;
+    assert!(matches!(pool.get().await, Err(PoolError::QueueFull)));
+
+    drop(obj);
+    waiting.await.unwrap().unwrap();
+    assert!(pool.get().await.is_ok());
+}
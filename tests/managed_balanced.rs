@@ -0,0 +1,138 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use deadpool::managed::{
+    self, BalanceStrategy, BalancedPool, BuildError, CircuitBreakerConfig, CircuitState, Metrics,
+    PoolConfig, PoolError, RecycleResult,
+};
+
+struct Manager {
+    fail: bool,
+    create_count: AtomicUsize,
+}
+
+impl Manager {
+    fn new(fail: bool) -> Self {
+        Self {
+            fail,
+            create_count: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        let _ = self.create_count.fetch_add(1, Ordering::Relaxed);
+        if self.fail {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+fn in_use(pool: &BalancedPool<Manager>) -> Vec<usize> {
+    pool.statuses().iter().map(|status| status.in_use).collect()
+}
+
+#[test]
+fn no_endpoints() {
+    let result = BalancedPool::<Manager>::builder().build();
+    assert!(matches!(result, Err(BuildError::NoEndpoints)));
+}
+
+#[tokio::test]
+async fn round_robin() {
+    let pool = BalancedPool::builder()
+        .endpoint(Manager::new(false))
+        .endpoint(Manager::new(false))
+        .endpoint(Manager::new(false))
+        .build()
+        .unwrap();
+    let mut objs = Vec::new();
+    for _ in 0..4 {
+        objs.push(pool.get().await.unwrap());
+    }
+    assert_eq!(in_use(&pool), vec![2, 1, 1]);
+}
+
+#[tokio::test]
+async fn least_connections() {
+    let pool = BalancedPool::builder()
+        .endpoint(Manager::new(false))
+        .endpoint(Manager::new(false))
+        .endpoint(Manager::new(false))
+        .strategy(BalanceStrategy::LeastConnections)
+        .build()
+        .unwrap();
+    let _obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    let _obj2 = pool.get().await.unwrap();
+    drop(obj1);
+    assert_eq!(in_use(&pool), vec![1, 0, 1]);
+    let _obj3 = pool.get().await.unwrap();
+    assert_eq!(in_use(&pool), vec![1, 1, 1]);
+}
+
+#[tokio::test]
+async fn weighted() {
+    let pool = BalancedPool::builder()
+        .weighted_endpoint(Manager::new(false), 3)
+        .weighted_endpoint(Manager::new(false), 1)
+        .strategy(BalanceStrategy::Weighted)
+        .pool_config(PoolConfig::new(8))
+        .build()
+        .unwrap();
+    let mut objs = Vec::new();
+    for _ in 0..8 {
+        objs.push(pool.get().await.unwrap());
+    }
+    assert_eq!(in_use(&pool), vec![6, 2]);
+}
+
+#[tokio::test]
+async fn failover() {
+    let pool = BalancedPool::builder()
+        .endpoint(Manager::new(true))
+        .endpoint(Manager::new(false))
+        .pool_config(PoolConfig {
+            circuit_breaker: Some(CircuitBreakerConfig::new(1, Duration::from_secs(60))),
+            ..PoolConfig::default()
+        })
+        .build()
+        .unwrap();
+
+    let _obj0 = pool.get().await.unwrap();
+    let statuses = pool.statuses();
+    assert_eq!(statuses[0].circuit, CircuitState::Open);
+    assert_eq!(statuses[1].in_use, 1);
+
+    // The unhealthy endpoint is skipped
+    let _obj1 = pool.get().await.unwrap();
+    let _obj2 = pool.get().await.unwrap();
+    assert_eq!(in_use(&pool), vec![0, 3]);
+}
+
+#[tokio::test]
+async fn all_endpoints_failing() {
+    let pool = BalancedPool::builder()
+        .endpoint(Manager::new(true))
+        .endpoint(Manager::new(true))
+        .build()
+        .unwrap();
+    assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+}
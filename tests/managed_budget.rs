@@ -0,0 +1,129 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::time;
+
+use deadpool::{
+    managed::{self, Metrics, Object, PoolBudget, PoolError, RecycleResult, TimeoutType},
+    Runtime,
+};
+
+struct Manager;
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+struct OtherManager;
+
+#[async_trait]
+impl managed::Manager for OtherManager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        Ok(42)
+    }
+
+    async fn recycle(&self, _conn: &mut usize, _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+fn pool<M: managed::Manager + 'static>(manager: M, budget: &PoolBudget) -> managed::Pool<M> {
+    managed::Pool::builder(manager)
+        .max_size(2)
+        .budget(budget.clone())
+        .create_timeout(Some(Duration::from_millis(20)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn shared_limit() {
+    let budget = PoolBudget::new(2);
+    let a = pool(Manager, &budget);
+    let b = pool(OtherManager, &budget);
+
+    let obj1 = a.get().await.unwrap();
+    let _obj2 = b.get().await.unwrap();
+    assert_eq!(budget.size(), 2);
+    assert!(matches!(
+        a.get().await,
+        Err(PoolError::Timeout(TimeoutType::Create))
+    ));
+    assert_eq!(a.stats().create_timeouts, 1);
+    assert_eq!(a.status().size, 1);
+
+    // The idle object of `a` is detached to make room for `b`
+    drop(obj1);
+    let _obj3 = b.get().await.unwrap();
+    assert_eq!(a.status().size, 0);
+    assert_eq!(a.stats().detached, 1);
+    assert_eq!(budget.size(), 2);
+}
+
+#[tokio::test]
+async fn evict_least_recently_used() {
+    let budget = PoolBudget::new(2);
+    let a = pool(Manager, &budget);
+    let b = pool(Manager, &budget);
+    let c = pool(OtherManager, &budget);
+
+    drop(a.get().await.unwrap());
+    time::sleep(Duration::from_millis(5)).await;
+    drop(b.get().await.unwrap());
+    let _obj = c.get().await.unwrap();
+    assert_eq!(a.status().size, 0);
+    assert_eq!(b.status().size, 1);
+}
+
+#[tokio::test]
+async fn wait_for_other_pool() {
+    let budget = PoolBudget::new(1);
+    let a = pool(Manager, &budget);
+    let b = pool(OtherManager, &budget);
+
+    let obj = a.get().await.unwrap();
+    let b_clone = b.clone();
+    let join = tokio::spawn(async move { b_clone.get().await.map(|_| ()) });
+    time::sleep(Duration::from_millis(5)).await;
+    drop(obj);
+    assert!(join.await.unwrap().is_ok());
+    assert_eq!(a.status().size, 0);
+    assert_eq!(b.status().size, 1);
+    assert_eq!(budget.size(), 1);
+}
+
+#[tokio::test]
+async fn released_when_leaving_pool() {
+    let budget = PoolBudget::new(2);
+    let a = pool(Manager, &budget);
+
+    let obj = a.get().await.unwrap();
+    let () = Object::take(obj);
+    assert_eq!(budget.size(), 0);
+
+    drop(a.get().await.unwrap());
+    assert_eq!(budget.size(), 1);
+    a.resize(0);
+    assert_eq!(budget.size(), 0);
+
+    let b = pool(Manager, &budget);
+    drop(b.get().await.unwrap());
+    drop(b);
+    assert_eq!(budget.size(), 0);
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tokio::time;
+
+use deadpool::managed::{
+    self, CircuitBreakerConfig, CircuitState, Metrics, PoolError, RecycleResult,
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    fail: AtomicBool,
+    create_count: AtomicUsize,
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        let _ = self.create_count.fetch_add(1, Ordering::Relaxed);
+        if self.fail.load(Ordering::Relaxed) {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn open_half_open_close() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(2)
+        .circuit_breaker(Some(CircuitBreakerConfig::new(
+            2,
+            Duration::from_millis(50),
+        )))
+        .build()
+        .unwrap();
+    let manager = pool.manager();
+    manager.fail.store(true, Ordering::Relaxed);
+
+    assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+    assert_eq!(pool.status().circuit, CircuitState::Closed);
+    assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+    assert_eq!(pool.status().circuit, CircuitState::Open);
+
+    // Fail fast without calling the manager
+    assert!(matches!(pool.get().await, Err(PoolError::CircuitOpen)));
+    assert_eq!(manager.create_count.load(Ordering::Relaxed), 2);
+
+    // Failing probe opens the circuit again
+    time::sleep(Duration::from_millis(60)).await;
+    assert_eq!(pool.status().circuit, CircuitState::HalfOpen);
+    assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+    assert_eq!(pool.status().circuit, CircuitState::Open);
+    assert!(matches!(pool.get().await, Err(PoolError::CircuitOpen)));
+    assert_eq!(manager.create_count.load(Ordering::Relaxed), 3);
+
+    // Successful probe closes the circuit
+    time::sleep(Duration::from_millis(60)).await;
+    manager.fail.store(false, Ordering::Relaxed);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(pool.status().circuit, CircuitState::Closed);
+    let _obj2 = pool.get().await.unwrap();
+    drop(obj);
+    assert_eq!(manager.create_count.load(Ordering::Relaxed), 5);
+}
+
+#[tokio::test]
+async fn idle_objects_while_open() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(2)
+        .circuit_breaker(Some(CircuitBreakerConfig::new(1, Duration::from_secs(60))))
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    pool.manager().fail.store(true, Ordering::Relaxed);
+    assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+    assert_eq!(pool.status().circuit, CircuitState::Open);
+    drop(obj);
+    assert!(pool.get().await.is_ok());
+}
@@ -0,0 +1,87 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use deadpool::{
+    managed::{self, Metrics, PoolError, RecycleResult, TimeoutType},
+    Runtime,
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    destroyed: AtomicUsize,
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+
+    async fn destroy(&self, _conn: ()) {
+        tokio::task::yield_now().await;
+        let _ = self.destroyed.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[tokio::test]
+async fn waits_for_objects() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(3)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    drop(obj0);
+
+    let close = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.close_gracefully(None).await })
+    };
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(pool.is_closed());
+    assert!(matches!(pool.get().await, Err(PoolError::Closed)));
+    assert_eq!(pool.manager().destroyed.load(Ordering::Relaxed), 1);
+    assert!(!close.is_finished());
+
+    drop(obj1);
+    assert!(close.await.unwrap().is_ok());
+    assert_eq!(pool.manager().destroyed.load(Ordering::Relaxed), 2);
+    let status = pool.status();
+    assert_eq!(status.size, 0);
+    assert_eq!(status.available, 0);
+}
+
+#[tokio::test]
+async fn timeout() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    assert!(matches!(
+        pool.close_gracefully(Some(Duration::from_millis(10))).await,
+        Err(PoolError::Timeout(TimeoutType::Wait))
+    ));
+
+    // Objects returned after the timeout are dropped without being destroyed.
+    drop(obj);
+    assert_eq!(pool.manager().destroyed.load(Ordering::Relaxed), 0);
+    assert_eq!(pool.status().size, 0);
+}
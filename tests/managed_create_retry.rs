@@ -0,0 +1,126 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+
+use deadpool::{
+    managed::{self, BuildError, CreateRetryConfig, Metrics, PoolError, RecycleResult, Timeouts},
+    Runtime,
+};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {
+    failures: usize,
+    retryable: bool,
+    attempts: AtomicUsize,
+}
+
+impl Manager {
+    fn new(failures: usize, retryable: bool) -> Self {
+        Self {
+            failures,
+            retryable,
+            attempts: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        if self.attempts.fetch_add(1, Ordering::Relaxed) < self.failures {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+
+    fn is_retryable(&self, _error: &()) -> bool {
+        self.retryable
+    }
+}
+
+fn retry(max_attempts: u32) -> Option<CreateRetryConfig> {
+    Some(CreateRetryConfig {
+        jitter: false,
+        ..CreateRetryConfig::new(
+            max_attempts,
+            Duration::from_millis(10),
+            Duration::from_millis(100),
+        )
+    })
+}
+
+#[test]
+fn no_runtime() {
+    let result = Pool::builder(Manager::new(0, true))
+        .create_retry(retry(3))
+        .build();
+    assert!(matches!(result, Err(BuildError::NoRuntimeSpecified)));
+}
+
+#[tokio::test]
+async fn retry_until_success() {
+    let pool = Pool::builder(Manager::new(2, true))
+        .create_retry(retry(3))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let start = Instant::now();
+    assert!(pool.get().await.is_ok());
+    // 10ms + 20ms backoff
+    assert!(start.elapsed() >= Duration::from_millis(30));
+    assert_eq!(pool.manager().attempts.load(Ordering::Relaxed), 3);
+}
+
+#[tokio::test]
+async fn max_attempts() {
+    let pool = Pool::builder(Manager::new(3, true))
+        .create_retry(retry(3))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+    assert_eq!(pool.manager().attempts.load(Ordering::Relaxed), 3);
+}
+
+#[tokio::test]
+async fn not_retryable() {
+    let pool = Pool::builder(Manager::new(1, false))
+        .create_retry(retry(3))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+    assert_eq!(pool.manager().attempts.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn bounded_by_create_timeout() {
+    let pool = Pool::builder(Manager::new(usize::MAX, true))
+        .create_retry(retry(10))
+        .timeouts(Timeouts {
+            create: Some(Duration::from_millis(25)),
+            ..Timeouts::default()
+        })
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let start = Instant::now();
+    assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+    assert!(start.elapsed() < Duration::from_millis(25));
+    // The third attempt would start after 10ms + 20ms
+    assert_eq!(pool.manager().attempts.load(Ordering::Relaxed), 2);
+}
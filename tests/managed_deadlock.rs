@@ -86,24 +86,28 @@ async fn pool_drained() {
     // let first task grab the only connection
     let get_1 = tokio::spawn(async move { pool_clone.get().await });
     task::yield_now().await;
-    assert_eq!(pool.status().size, 0);
+    assert_eq!(pool.status().size, 1);
     assert_eq!(pool.status().available, 0);
-    assert_eq!(pool.status().waiting, 1);
+    assert_eq!(pool.status().creating, 1);
+    assert_eq!(pool.status().waiting, 0);
 
     // let second task wait for the connection
     let pool_clone = pool.clone();
     let get_2 = tokio::spawn(async move { pool_clone.get().await });
     task::yield_now().await;
-    assert_eq!(pool.status().size, 0);
+    assert_eq!(pool.status().size, 1);
     assert_eq!(pool.status().available, 0);
-    assert_eq!(pool.status().waiting, 2);
+    assert_eq!(pool.status().creating, 1);
+    assert_eq!(pool.status().waiting, 1);
 
     // first task receives an error
     rc.create_err();
     assert!(get_1.await.unwrap().is_err());
-    assert_eq!(pool.status().size, 0);
+    task::yield_now().await;
+    assert_eq!(pool.status().size, 1);
     assert_eq!(pool.status().available, 0);
-    assert_eq!(pool.status().waiting, 1);
+    assert_eq!(pool.status().creating, 1);
+    assert_eq!(pool.status().waiting, 0);
 
     // the second task should now be able to create an object
     rc.create_ok();
@@ -111,6 +115,7 @@ async fn pool_drained() {
     assert!(get_2_result.is_ok(), "get_2 should not time out");
     assert_eq!(pool.status().size, 1);
     assert_eq!(pool.status().available, 0);
+    assert_eq!(pool.status().in_use, 1);
     assert_eq!(pool.status().waiting, 0);
     assert!(
         get_2_result.unwrap().unwrap().is_ok(),
@@ -0,0 +1,135 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tokio::time;
+
+use deadpool::{
+    managed::{self, BuildError, Metrics, RecycleResult},
+    Runtime,
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    created: AtomicUsize,
+    recycled: AtomicUsize,
+    detached: AtomicUsize,
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _conn: &mut usize, _: &Metrics) -> RecycleResult<()> {
+        let _ = self.recycled.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn detach(&self, _obj: &mut usize) {
+        let _ = self.detached.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn no_runtime() {
+    let result = Pool::builder(Manager::default())
+        .max_lifetime(Some(Duration::from_secs(1)))
+        .build();
+    assert!(matches!(result, Err(BuildError::NoRuntimeSpecified)));
+    let result = Pool::builder(Manager::default())
+        .idle_timeout(Some(Duration::from_secs(1)))
+        .build();
+    assert!(matches!(result, Err(BuildError::NoRuntimeSpecified)));
+}
+
+#[test]
+fn zero() {
+    let result = Pool::builder(Manager::default())
+        .max_lifetime(Some(Duration::ZERO))
+        .runtime(Runtime::Tokio1)
+        .build();
+    assert!(matches!(result, Err(BuildError::InvalidConfig(_))));
+    let result = Pool::builder(Manager::default())
+        .idle_timeout(Some(Duration::ZERO))
+        .runtime(Runtime::Tokio1)
+        .build();
+    assert!(matches!(result, Err(BuildError::InvalidConfig(_))));
+}
+
+#[tokio::test]
+async fn reap_max_lifetime() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(4)
+        .max_lifetime(Some(Duration::from_millis(20)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    {
+        let _a = pool.get().await.unwrap();
+        let _b = pool.get().await.unwrap();
+    }
+    assert_eq!(pool.status().size, 2);
+    time::sleep(Duration::from_millis(60)).await;
+    assert_eq!(pool.status().size, 0);
+    assert_eq!(pool.manager().detached.load(Ordering::Relaxed), 2);
+}
+
+#[tokio::test]
+async fn reap_idle_timeout() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(4)
+        .idle_timeout(Some(Duration::from_millis(20)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.status().size, 1);
+    time::sleep(Duration::from_millis(60)).await;
+    assert_eq!(pool.status().size, 0);
+    assert_eq!(pool.manager().detached.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn keep_checked_out() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(4)
+        .max_lifetime(Some(Duration::from_millis(20)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    time::sleep(Duration::from_millis(60)).await;
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.manager().detached.load(Ordering::Relaxed), 0);
+    drop(obj);
+}
+
+#[tokio::test]
+async fn reject_on_checkout() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(4)
+        .max_lifetime(Some(Duration::from_millis(50)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 0);
+    time::sleep(Duration::from_millis(60)).await;
+    drop(obj);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 1);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.manager().recycled.load(Ordering::Relaxed), 0);
+    assert_eq!(pool.manager().detached.load(Ordering::Relaxed), 1);
+}
@@ -0,0 +1,138 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tokio::{task, time};
+
+use deadpool::{
+    managed::{self, Metrics, PoolError, RecycleResult, TimeoutType, Timeouts},
+    Runtime,
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    create_count: AtomicUsize,
+    fail_after: Option<usize>,
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        let count = self.create_count.fetch_add(1, Ordering::Relaxed);
+        match self.fail_after {
+            Some(fail_after) if count >= fail_after => Err(()),
+            _ => Ok(count),
+        }
+    }
+
+    async fn recycle(&self, _conn: &mut usize, _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+fn pool(manager: Manager) -> Pool {
+    Pool::builder(manager)
+        .max_size(3)
+        .wait_timeout(Some(Duration::from_millis(20)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn basic() {
+    let pool = pool(Manager::default());
+    let objs = pool.get_many(3).await.unwrap();
+    let mut values = objs.iter().map(|obj| **obj).collect::<Vec<_>>();
+    values.sort_unstable();
+    assert_eq!(values, vec![0, 1, 2]);
+    assert_eq!(pool.status().in_use, 3);
+
+    drop(objs);
+    assert_eq!(pool.status().available, 3);
+    assert!(pool.get_many(0).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn all_or_nothing() {
+    let pool = pool(Manager::default());
+    let obj = pool.get().await.unwrap();
+
+    // Only two slots are left, so none of them is taken
+    assert!(matches!(
+        pool.get_many(3).await,
+        Err(PoolError::Timeout(TimeoutType::Wait))
+    ));
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.in_use, 1);
+
+    let timeouts = Timeouts::wait_millis(0);
+    let objs = pool.timeout_get_many(2, &timeouts).await.unwrap();
+    assert_eq!(objs.len(), 2);
+    drop(obj);
+}
+
+#[tokio::test]
+async fn no_deadlock() {
+    let pool = pool(Manager::default());
+    let mut handles = Vec::new();
+    for _ in 0..4 {
+        let pool = pool.clone();
+        handles.push(tokio::spawn(async move {
+            let objs = pool.timeout_get_many(2, &Timeouts::new()).await.unwrap();
+            task::yield_now().await;
+            drop(objs);
+        }));
+    }
+    let join = async {
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    };
+    time::timeout(Duration::from_secs(1), join).await.unwrap();
+    assert_eq!(pool.status().in_use, 0);
+}
+
+#[tokio::test]
+async fn failure_returns_objects() {
+    let pool = pool(Manager {
+        fail_after: Some(2),
+        ..Manager::default()
+    });
+    assert!(matches!(
+        pool.get_many(3).await,
+        Err(PoolError::Backend(()))
+    ));
+    let status = pool.status();
+    assert_eq!(status.size, 2);
+    assert_eq!(status.available, 2);
+    assert_eq!(status.in_use, 0);
+
+    let objs = pool.get_many(2).await.unwrap();
+    assert_eq!(objs.len(), 2);
+}
+
+#[tokio::test]
+async fn too_many_objects() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(3)
+        .high_priority_reserve(1)
+        .build()
+        .unwrap();
+    assert!(matches!(
+        pool.get_many(3).await,
+        Err(PoolError::TooManyObjects)
+    ));
+    assert_eq!(pool.status().waiting, 0);
+    assert_eq!(pool.get_many(2).await.unwrap().len(), 2);
+}
@@ -0,0 +1,140 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tokio::time;
+
+use deadpool::{
+    managed::{
+        self, BuildError, KeyedPool, Metrics, PoolConfig, PoolError, RecycleResult, Timeouts,
+    },
+    Runtime,
+};
+
+struct Manager {
+    created: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        let _ = self.created.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+fn pool(max_size: usize, max_pools: Option<usize>) -> (KeyedPool<u32, Manager>, Arc<AtomicUsize>) {
+    let created = Arc::new(AtomicUsize::new(0));
+    let factory_created = created.clone();
+    let pool = KeyedPool::builder(move |_: &u32| Manager {
+        created: factory_created.clone(),
+    })
+    .max_size(max_size)
+    .max_pools(max_pools)
+    .pool_config(PoolConfig {
+        timeouts: Timeouts {
+            wait: Some(Duration::from_millis(20)),
+            create: Some(Duration::from_millis(20)),
+            recycle: None,
+        },
+        ..PoolConfig::new(2)
+    })
+    .runtime(Runtime::Tokio1)
+    .build()
+    .unwrap();
+    (pool, created)
+}
+
+#[test]
+fn no_runtime() {
+    let result = KeyedPool::<u32, Manager>::builder(|_| Manager {
+        created: Arc::default(),
+    })
+    .pool_config(PoolConfig {
+        timeouts: Timeouts::wait_millis(10),
+        ..PoolConfig::default()
+    })
+    .build();
+    assert!(matches!(result, Err(BuildError::NoRuntimeSpecified)));
+}
+
+#[tokio::test]
+async fn per_key_limit() {
+    let (pool, _) = pool(10, None);
+    let _a1 = pool.get(&1).await.unwrap();
+    let _a2 = pool.get(&1).await.unwrap();
+    assert!(matches!(pool.get(&1).await, Err(PoolError::Timeout(_))));
+    let _b = pool.get(&2).await.unwrap();
+    assert_eq!(pool.size(), 3);
+
+    let statuses = pool.statuses();
+    assert_eq!(statuses.len(), 2);
+    assert_eq!(statuses[&1].in_use, 2);
+    assert_eq!(statuses[&2].in_use, 1);
+    assert!(pool.status(&3).is_none());
+}
+
+#[tokio::test]
+async fn global_limit() {
+    let (pool, created) = pool(2, None);
+    let a = pool.get(&1).await.unwrap();
+    let _b = pool.get(&2).await.unwrap();
+    assert!(matches!(pool.get(&3).await, Err(PoolError::Timeout(_))));
+    assert_eq!(pool.size(), 2);
+
+    // The idle object of key 1 is detached to make room for key 3
+    drop(a);
+    assert_eq!(pool.status(&1).unwrap().available, 1);
+    let _c = pool.get(&3).await.unwrap();
+    assert_eq!(pool.status(&1).unwrap().size, 0);
+    assert_eq!(pool.size(), 2);
+    assert_eq!(created.load(Ordering::Relaxed), 3);
+}
+
+#[tokio::test]
+async fn wait_for_other_key() {
+    let (pool, _) = pool(1, None);
+    let a = pool.get(&1).await.unwrap();
+    let pool_clone = pool.clone();
+    let join = tokio::spawn(async move { pool_clone.get(&2).await.map(|_| ()) });
+    time::sleep(Duration::from_millis(5)).await;
+    drop(a);
+    assert!(join.await.unwrap().is_ok());
+    assert_eq!(pool.size(), 1);
+}
+
+#[tokio::test]
+async fn take_frees_budget() {
+    let (pool, _) = pool(1, None);
+    let obj = pool.get(&1).await.unwrap();
+    let () = managed::Object::take(obj);
+    assert_eq!(pool.size(), 0);
+    assert!(pool.get(&2).await.is_ok());
+}
+
+#[tokio::test]
+async fn evict_least_recently_used_pool() {
+    let (pool, _) = pool(10, Some(2));
+    drop(pool.get(&1).await.unwrap());
+    drop(pool.get(&2).await.unwrap());
+    drop(pool.get(&1).await.unwrap());
+    drop(pool.get(&3).await.unwrap());
+    assert!(pool.status(&1).is_some());
+    assert!(pool.status(&2).is_none());
+    assert!(pool.status(&3).is_some());
+    assert_eq!(pool.size(), 2);
+}
@@ -0,0 +1,93 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use deadpool::{
+    managed::{self, BuildError, CheckoutInfo, Metrics, PoolObserver, RecycleResult},
+    Runtime,
+};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default)]
+struct Observer {
+    leaks: Arc<Mutex<Vec<CheckoutInfo>>>,
+}
+
+impl PoolObserver<Manager> for Observer {
+    fn leak_detected(&self, info: &CheckoutInfo) {
+        self.leaks.lock().unwrap().push(*info);
+    }
+}
+
+#[test]
+fn no_runtime() {
+    let result = Pool::builder(Manager {})
+        .leak_detection_threshold(Some(Duration::from_millis(50)))
+        .build();
+    assert!(matches!(result, Err(BuildError::NoRuntimeSpecified)));
+}
+
+#[test]
+fn zero_threshold() {
+    let result = Pool::builder(Manager {})
+        .leak_detection_threshold(Some(Duration::ZERO))
+        .runtime(Runtime::Tokio1)
+        .build();
+    assert!(matches!(result, Err(BuildError::InvalidConfig(_))));
+}
+
+#[tokio::test]
+async fn leak_detected() {
+    let observer = Observer::default();
+    let pool = Pool::builder(Manager {})
+        .leak_detection_threshold(Some(Duration::from_millis(50)))
+        .observer(observer.clone())
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+
+    drop(pool.get().await.unwrap());
+    let line = line!() + 1;
+    let obj = pool.get().await.unwrap();
+
+    let checked_out = pool.checked_out();
+    assert_eq!(checked_out.len(), 1);
+    assert_eq!(checked_out[0].location.file(), file!());
+    assert_eq!(checked_out[0].location.line(), line);
+    assert!(observer.leaks.lock().unwrap().is_empty());
+
+    // The leak is reported only once
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    {
+        let leaks = observer.leaks.lock().unwrap();
+        assert_eq!(leaks.len(), 1);
+        assert_eq!(leaks[0].location.line(), line);
+        assert_eq!(leaks[0].metrics.recycle_count, 1);
+        assert!(leaks[0].duration() >= Duration::from_millis(50));
+    }
+
+    drop(obj);
+    assert!(pool.checked_out().is_empty());
+}
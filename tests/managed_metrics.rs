@@ -0,0 +1,132 @@
+#![cfg(all(feature = "managed", feature = "metrics"))]
+
+use std::{collections::HashMap, sync::Once};
+
+use async_trait::async_trait;
+use metrics_util::debugging::{DebugValue, DebuggingRecorder, Snapshotter};
+
+use deadpool::managed::{self, BalancedPool, KeyedPool, Metrics, RecycleError, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Err(RecycleError::StaticMessage("fail"))
+    }
+}
+
+fn install_recorder() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| DebuggingRecorder::per_thread().install().unwrap());
+}
+
+fn snapshot(pool: &str) -> HashMap<String, DebugValue> {
+    Snapshotter::current_thread_snapshot()
+        .unwrap()
+        .into_vec()
+        .into_iter()
+        .filter(|(key, ..)| {
+            key.key()
+                .labels()
+                .any(|label| label.key() == "pool" && label.value() == pool)
+        })
+        .map(|(key, _, _, value)| (key.key().name().to_string(), value))
+        .collect()
+}
+
+#[tokio::test]
+async fn export() {
+    install_recorder();
+
+    let pool = Pool::builder(Manager {})
+        .max_size(4)
+        .name("test")
+        .build()
+        .unwrap();
+    assert_eq!(pool.name(), Some("test"));
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+
+    let metrics = snapshot("test");
+    assert!(matches!(
+        metrics["deadpool_pool_max_size"],
+        DebugValue::Gauge(v) if v.into_inner() == 4.0
+    ));
+    assert!(matches!(
+        metrics["deadpool_pool_size"],
+        DebugValue::Gauge(v) if v.into_inner() == 1.0
+    ));
+    assert!(matches!(
+        metrics["deadpool_pool_available"],
+        DebugValue::Gauge(v) if v.into_inner() == 1.0
+    ));
+    assert!(matches!(
+        metrics["deadpool_recycle_errors_total"],
+        DebugValue::Counter(1)
+    ));
+    assert!(matches!(
+        metrics["deadpool_detached_total"],
+        DebugValue::Counter(1)
+    ));
+    assert!(matches!(
+        &metrics["deadpool_create_duration_seconds"],
+        DebugValue::Histogram(v) if v.len() == 2
+    ));
+    assert!(matches!(
+        &metrics["deadpool_checkout_wait_seconds"],
+        DebugValue::Histogram(v) if v.len() == 2
+    ));
+}
+
+#[tokio::test]
+async fn keyed_sub_pools() {
+    install_recorder();
+
+    let pool = KeyedPool::builder(|_: &&str| Manager {})
+        .max_size(4)
+        .name("keyed_test")
+        .build()
+        .unwrap();
+    let _a = pool.get(&"a").await.unwrap();
+    let _b0 = pool.get(&"b").await.unwrap();
+    let _b1 = pool.get(&"b").await.unwrap();
+
+    for (pool, in_use) in [(r#"keyed_test["a"]"#, 1.0), (r#"keyed_test["b"]"#, 2.0)] {
+        assert!(matches!(
+            snapshot(pool)["deadpool_pool_in_use"],
+            DebugValue::Gauge(v) if v.into_inner() == in_use
+        ));
+    }
+}
+
+#[tokio::test]
+async fn balanced_sub_pools() {
+    install_recorder();
+
+    let pool = BalancedPool::builder()
+        .endpoint(Manager {})
+        .endpoint(Manager {})
+        .name("balanced_test")
+        .build()
+        .unwrap();
+    let _obj0 = pool.get().await.unwrap();
+    let _obj1 = pool.get().await.unwrap();
+    let _obj2 = pool.get().await.unwrap();
+
+    for (pool, in_use) in [("balanced_test[0]", 2.0), ("balanced_test[1]", 1.0)] {
+        assert!(matches!(
+            snapshot(pool)["deadpool_pool_in_use"],
+            DebugValue::Gauge(v) if v.into_inner() == in_use
+        ));
+    }
+}
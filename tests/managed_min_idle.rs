@@ -0,0 +1,154 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tokio::time;
+
+use deadpool::{
+    managed::{self, BuildError, Metrics, RecycleResult},
+    Runtime,
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    created: AtomicUsize,
+    fail: bool,
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        let created = self.created.fetch_add(1, Ordering::Relaxed);
+        if self.fail {
+            Err(())
+        } else {
+            Ok(created)
+        }
+    }
+
+    async fn recycle(&self, _conn: &mut usize, _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+async fn settle() {
+    time::sleep(Duration::from_millis(10)).await;
+}
+
+#[test]
+fn no_runtime() {
+    let result = Pool::builder(Manager::default())
+        .max_size(4)
+        .min_idle(2)
+        .build();
+    assert!(matches!(result, Err(BuildError::NoRuntimeSpecified)));
+}
+
+#[tokio::test]
+async fn fill_on_build() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(4)
+        .min_idle(2)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    settle().await;
+    let status = pool.status();
+    assert_eq!(status.size, 2);
+    assert_eq!(status.available, 2);
+}
+
+#[tokio::test]
+async fn refill_after_checkout() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(4)
+        .min_idle(2)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    settle().await;
+    let _obj = pool.get().await.unwrap();
+    settle().await;
+    let status = pool.status();
+    assert_eq!(status.size, 3);
+    assert_eq!(status.available, 2);
+}
+
+#[tokio::test]
+async fn refill_after_retain() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(4)
+        .min_idle(2)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    settle().await;
+    pool.retain(|_, _| false);
+    settle().await;
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.manager().created.load(Ordering::Relaxed), 4);
+}
+
+#[tokio::test]
+async fn respect_max_size() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(2)
+        .min_idle(2)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    settle().await;
+    let _obj0 = pool.get().await.unwrap();
+    let _obj1 = pool.get().await.unwrap();
+    settle().await;
+    let status = pool.status();
+    assert_eq!(status.size, 2);
+    assert_eq!(status.available, 0);
+}
+
+#[tokio::test]
+async fn stop_on_close() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(4)
+        .min_idle(2)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    settle().await;
+    pool.close();
+    settle().await;
+    assert_eq!(pool.status().size, 0);
+    assert_eq!(pool.manager().created.load(Ordering::Relaxed), 2);
+}
+
+#[tokio::test]
+async fn back_off_after_failure() {
+    let manager = Manager {
+        fail: true,
+        ..Manager::default()
+    };
+    let pool = Pool::builder(manager)
+        .max_size(4)
+        .min_idle(1)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    settle().await;
+    assert_eq!(pool.manager().created.load(Ordering::Relaxed), 1);
+
+    // Checkouts don't cut the back-off short
+    for _ in 0..3 {
+        assert!(pool.get().await.is_err());
+    }
+    settle().await;
+    assert_eq!(pool.manager().created.load(Ordering::Relaxed), 4);
+}
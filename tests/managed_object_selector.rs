@@ -0,0 +1,108 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    collections::HashSet,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use async_trait::async_trait;
+
+use deadpool::managed::{self, IdleObjects, Metrics, QueueMode, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    create_count: AtomicUsize,
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        Ok(self.create_count.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _conn: &mut usize, _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+fn pool(queue_mode: QueueMode) -> Pool {
+    Pool::builder(Manager::default())
+        .max_size(3)
+        .queue_mode(queue_mode)
+        .build()
+        .unwrap()
+}
+
+/// Creates three objects and returns them in the given order.
+async fn fill(pool: &Pool, order: [usize; 3]) {
+    let mut objs = [
+        Some(pool.get().await.unwrap()),
+        Some(pool.get().await.unwrap()),
+        Some(pool.get().await.unwrap()),
+    ];
+    for &index in &order {
+        drop(objs[index].take());
+    }
+}
+
+#[tokio::test]
+async fn least_recycled() {
+    let pool = pool(QueueMode::LeastRecycled);
+    let obj0 = pool.get().await.unwrap();
+    let obj1 = pool.get().await.unwrap();
+    let obj2 = pool.get().await.unwrap();
+    drop(obj2);
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    drop(obj0);
+    drop(obj1);
+    // Object `2` has been recycled twice while the others haven't been
+    assert_eq!(*pool.get().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn newest() {
+    let pool = pool(QueueMode::Newest);
+    fill(&pool, [0, 2, 1]).await;
+    assert_eq!(*pool.get().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn random() {
+    let pool = pool(QueueMode::Random);
+    fill(&pool, [0, 1, 2]).await;
+    let mut values = HashSet::new();
+    for _ in 0..100 {
+        let _ = values.insert(*pool.get().await.unwrap());
+    }
+    assert!(values.len() > 1);
+}
+
+#[tokio::test]
+async fn custom() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(3)
+        .queue_mode(QueueMode::Fifo)
+        .object_selector(|idle: &IdleObjects<'_>| idle.len() - 1)
+        .build()
+        .unwrap();
+    fill(&pool, [0, 2, 1]).await;
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn out_of_bounds() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(3)
+        .object_selector(|_: &IdleObjects<'_>| usize::MAX)
+        .build()
+        .unwrap();
+    fill(&pool, [2, 0, 1]).await;
+    assert_eq!(*pool.get().await.unwrap(), 2);
+    assert_eq!(pool.status().size, 3);
+}
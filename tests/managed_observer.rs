@@ -0,0 +1,167 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use deadpool::managed::{
+    self, Hook, HookError, HookType, Metrics, PoolError, PoolObserver, RecycleError, RecycleResult,
+    TimeoutType, Timeouts,
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    create_fail: AtomicBool,
+    recycle_fail: AtomicBool,
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        if self.create_fail.load(Ordering::Relaxed) {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        if self.recycle_fail.load(Ordering::Relaxed) {
+            Err(RecycleError::StaticMessage("recycle failed"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct Observer {
+    events: Arc<Mutex<Vec<String>>>,
+    waits: Arc<AtomicUsize>,
+}
+
+impl Observer {
+    fn push(&self, event: &str) {
+        self.events.lock().unwrap().push(event.to_string());
+    }
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.events.lock().unwrap())
+    }
+}
+
+impl PoolObserver<Manager> for Observer {
+    fn checkout_wait(&self, _duration: Duration) {
+        let _ = self.waits.fetch_add(1, Ordering::Relaxed);
+    }
+    fn create_success(&self, _duration: Duration) {
+        self.push("create_success");
+    }
+    fn create_failure(&self, _duration: Duration, _error: &PoolError<()>) {
+        self.push("create_failure");
+    }
+    fn recycle_success(&self, _duration: Duration) {
+        self.push("recycle_success");
+    }
+    fn recycle_failure(&self, _duration: Duration, error: &RecycleError<()>) {
+        assert!(matches!(error, RecycleError::StaticMessage(_)));
+        self.push("recycle_failure");
+    }
+    fn hook_failure(&self, hook_type: HookType, _error: &HookError<()>) {
+        self.push(&format!("hook_failure({:?})", hook_type));
+    }
+    fn detach(&self, _metrics: &Metrics) {
+        self.push("detach");
+    }
+    fn timeout(&self, timeout_type: TimeoutType) {
+        self.push(&format!("timeout({:?})", timeout_type));
+    }
+}
+
+#[tokio::test]
+async fn create_and_recycle() {
+    let observer = Observer::default();
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .observer(observer.clone())
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    assert_eq!(observer.take(), ["create_success"]);
+    drop(pool.get().await.unwrap());
+    assert_eq!(observer.take(), ["recycle_success"]);
+    pool.manager().recycle_fail.store(true, Ordering::Relaxed);
+    drop(pool.get().await.unwrap());
+    assert_eq!(
+        observer.take(),
+        ["recycle_failure", "detach", "create_success"]
+    );
+    pool.manager().create_fail.store(true, Ordering::Relaxed);
+    pool.retain(|_, _| false);
+    assert!(pool.get().await.is_err());
+    assert_eq!(observer.take(), ["detach", "create_failure"]);
+    assert_eq!(observer.waits.load(Ordering::Relaxed), 4);
+}
+
+#[tokio::test]
+async fn hook_failure() {
+    let observer = Observer::default();
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .pre_recycle(Hook::sync_fn(|_, _| Err(HookError::StaticMessage("fail"))))
+        .observer(observer.clone())
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    assert_eq!(
+        observer.take(),
+        [
+            "create_success",
+            "hook_failure(PreRecycle)",
+            "detach",
+            "create_success"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn wait_timeout() {
+    let observer = Observer::default();
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .observer(observer.clone())
+        .build()
+        .unwrap();
+    let _obj = pool.get().await.unwrap();
+    let result = pool
+        .timeout_get(&Timeouts {
+            wait: Some(Duration::ZERO),
+            ..pool.timeouts()
+        })
+        .await;
+    assert!(matches!(result, Err(PoolError::Timeout(TimeoutType::Wait))));
+    assert_eq!(observer.take(), ["create_success", "timeout(Wait)"]);
+}
+
+#[tokio::test]
+async fn object_take() {
+    let observer = Observer::default();
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .observer(observer.clone())
+        .build()
+        .unwrap();
+    let () = managed::Object::take(pool.get().await.unwrap());
+    assert_eq!(observer.take(), ["create_success", "detach"]);
+}
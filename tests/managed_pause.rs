@@ -0,0 +1,147 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use deadpool::{
+    managed::{self, Metrics, PauseOptions, PoolError, RecycleResult, TimeoutType, Timeouts},
+    Runtime,
+};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+fn pool() -> Pool {
+    Pool::builder(Manager {})
+        .max_size(2)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn wait_until_resumed() {
+    let pool = pool();
+    drop(pool.get().await.unwrap());
+    pool.pause(PauseOptions::default());
+    assert!(pool.is_paused());
+    assert_eq!(pool.status().available, 1);
+
+    let waiting = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get().await.map(drop) })
+    };
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(!waiting.is_finished());
+    assert_eq!(pool.status().waiting, 1);
+
+    pool.resume();
+    assert!(!pool.is_paused());
+    waiting.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn wait_timeout() {
+    let pool = pool();
+    pool.pause(PauseOptions::default());
+    assert!(matches!(
+        pool.timeout_get(&Timeouts::wait_millis(10)).await,
+        Err(PoolError::Timeout(TimeoutType::Wait))
+    ));
+    assert!(matches!(
+        pool.timeout_get(&Timeouts::wait_millis(0)).await,
+        Err(PoolError::Timeout(TimeoutType::Wait))
+    ));
+}
+
+#[tokio::test]
+async fn fail_fast() {
+    let pool = pool();
+    let obj = pool.get().await.unwrap();
+    pool.pause(PauseOptions {
+        fail_fast: true,
+        ..PauseOptions::default()
+    });
+    assert!(matches!(pool.get().await, Err(PoolError::Paused)));
+
+    // Checked out objects are not affected
+    drop(obj);
+    assert_eq!(pool.status().available, 1);
+
+    pool.resume();
+    assert!(pool.get().await.is_ok());
+}
+
+#[tokio::test]
+async fn drop_idle() {
+    let pool = pool();
+    let obj0 = pool.get().await.unwrap();
+    drop(pool.get().await.unwrap());
+    pool.pause(PauseOptions {
+        drop_idle: true,
+        ..PauseOptions::default()
+    });
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 0);
+
+    drop(obj0);
+    pool.resume();
+    assert_eq!(pool.status().available, 1);
+}
+
+#[tokio::test]
+async fn close_while_paused() {
+    let pool = pool();
+    pool.pause(PauseOptions::default());
+    let waiting = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get().await.map(drop) })
+    };
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(!waiting.is_finished());
+
+    pool.close();
+    assert!(matches!(waiting.await.unwrap(), Err(PoolError::Closed)));
+    assert!(matches!(pool.get().await, Err(PoolError::Closed)));
+    assert!(matches!(
+        pool.timeout_get(&Timeouts::wait_millis(0)).await,
+        Err(PoolError::Closed)
+    ));
+}
+
+#[tokio::test]
+async fn max_waiting() {
+    let pool = Pool::builder(Manager {})
+        .max_size(2)
+        .max_waiting(Some(1))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    pool.pause(PauseOptions::default());
+    let waiting = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get().await.map(drop) })
+    };
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(matches!(pool.get().await, Err(PoolError::QueueFull)));
+
+    pool.resume();
+    waiting.await.unwrap().unwrap();
+}
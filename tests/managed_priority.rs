@@ -0,0 +1,101 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use deadpool::managed::{self, Metrics, PoolError, Priority, RecycleResult, TimeoutType, Timeouts};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn served_by_priority() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let obj = pool.get().await.unwrap();
+
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let mut handles = Vec::new();
+    let priorities = vec![
+        (Priority::Low, 0),
+        (Priority::Normal, 1),
+        (Priority::High, 2),
+        (Priority::Normal, 3),
+        (Priority::High, 4),
+    ];
+    for (priority, id) in priorities {
+        let pool = pool.clone();
+        let order = order.clone();
+        handles.push(tokio::spawn(async move {
+            let _obj = pool.get_with_priority(priority).await.unwrap();
+            order.lock().unwrap().push(id);
+        }));
+        // Make sure the tasks are enqueued in the given order.
+        tokio::task::yield_now().await;
+    }
+    assert_eq!(pool.status().waiting, 5);
+
+    drop(obj);
+    for handle in handles {
+        handle.await.unwrap();
+    }
+    assert_eq!(*order.lock().unwrap(), [2, 4, 1, 3, 0]);
+}
+
+#[tokio::test]
+async fn high_priority_reserve() {
+    let pool = Pool::builder(Manager {})
+        .max_size(3)
+        .high_priority_reserve(1)
+        .build()
+        .unwrap();
+    let no_wait = Timeouts {
+        wait: Some(Duration::ZERO),
+        ..pool.timeouts()
+    };
+
+    let _obj1 = pool.get().await.unwrap();
+    let obj2 = pool
+        .timeout_get_with_priority(&no_wait, Priority::Low)
+        .await
+        .unwrap();
+    assert!(matches!(
+        pool.timeout_get(&no_wait).await,
+        Err(PoolError::Timeout(TimeoutType::Wait))
+    ));
+    let obj3 = pool
+        .timeout_get_with_priority(&no_wait, Priority::High)
+        .await
+        .unwrap();
+
+    // A waiting task with a lower priority must not get the reserved slot.
+    let waiting = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get().await.map(drop) })
+    };
+    tokio::task::yield_now().await;
+    drop(obj3);
+    tokio::task::yield_now().await;
+    assert!(!waiting.is_finished());
+
+    drop(obj2);
+    waiting.await.unwrap().unwrap();
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use deadpool::managed::{self, Hook, Metrics, Object, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    recycle_interval: Option<Duration>,
+    recycle_count: AtomicUsize,
+}
+
+impl Manager {
+    fn recycle_count(&self) -> usize {
+        self.recycle_count.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        let _ = self.recycle_count.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn recycle_interval(&self) -> Option<Duration> {
+        self.recycle_interval
+    }
+}
+
+#[tokio::test]
+async fn skip_recently_recycled() {
+    let hook_count = Arc::new(AtomicUsize::new(0));
+    let count = hook_count.clone();
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .recycle_interval(Some(Duration::from_secs(60)))
+        .pre_recycle(Hook::sync_fn(move |_, _| {
+            let _ = count.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }))
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    let obj = pool.get().await.unwrap();
+    assert_eq!(pool.manager().recycle_count(), 0);
+    assert_eq!(hook_count.load(Ordering::Relaxed), 1);
+    assert_eq!(Object::metrics(&obj).recycle_count, 0);
+    assert!(Object::metrics(&obj).recycled.is_none());
+}
+
+#[tokio::test]
+async fn recycle_after_interval() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .recycle_interval(Some(Duration::from_millis(10)))
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.manager().recycle_count(), 1);
+}
+
+#[tokio::test]
+async fn manager_default() {
+    let manager = Manager {
+        recycle_interval: Some(Duration::from_secs(60)),
+        ..Manager::default()
+    };
+    let pool = Pool::builder(manager).max_size(1).build().unwrap();
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.manager().recycle_count(), 0);
+
+    // The configured interval takes precedence
+    let manager = Manager {
+        recycle_interval: Some(Duration::from_secs(60)),
+        ..Manager::default()
+    };
+    let pool = Pool::builder(manager)
+        .max_size(1)
+        .recycle_interval(Some(Duration::ZERO))
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.manager().recycle_count(), 1);
+}
@@ -0,0 +1,169 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tokio::time;
+
+use deadpool::{
+    managed::{self, BuildError, Metrics, Object, RecycleError, RecycleMode, RecycleResult},
+    Runtime,
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    recycle_count: AtomicUsize,
+    fail_recycle: AtomicBool,
+}
+
+impl Manager {
+    fn recycle_count(&self) -> usize {
+        self.recycle_count.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        let _ = self.recycle_count.fetch_add(1, Ordering::Relaxed);
+        if self.fail_recycle.load(Ordering::Relaxed) {
+            Err(RecycleError::StaticMessage("recycling failed"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn pool(recycle_mode: RecycleMode) -> Pool {
+    Pool::builder(Manager::default())
+        .max_size(2)
+        .recycle_mode(recycle_mode)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn no_runtime() {
+    let result = Pool::builder(Manager::default())
+        .recycle_mode(RecycleMode::OnReturn)
+        .build();
+    assert!(matches!(result, Err(BuildError::NoRuntimeSpecified)));
+}
+
+#[test]
+fn zero_interval() {
+    let result = Pool::builder(Manager::default())
+        .recycle_mode(RecycleMode::Background {
+            interval: Duration::ZERO,
+        })
+        .runtime(Runtime::Tokio1)
+        .build();
+    assert!(matches!(result, Err(BuildError::InvalidConfig(_))));
+}
+
+#[tokio::test]
+async fn on_return() {
+    let pool = pool(RecycleMode::OnReturn);
+    drop(pool.get().await.unwrap());
+    let status = pool.status();
+    assert_eq!(status.recycling, 1);
+    assert_eq!(status.available, 0);
+
+    time::sleep(Duration::from_millis(10)).await;
+    let status = pool.status();
+    assert_eq!(status.recycling, 0);
+    assert_eq!(status.available, 1);
+    assert_eq!(pool.manager().recycle_count(), 1);
+
+    // The object is handed out without recycling it again
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::metrics(&obj).recycle_count, 1);
+    assert_eq!(pool.manager().recycle_count(), 1);
+}
+
+#[tokio::test]
+async fn on_return_failure() {
+    let pool = pool(RecycleMode::OnReturn);
+    pool.manager().fail_recycle.store(true, Ordering::Relaxed);
+    drop(pool.get().await.unwrap());
+    time::sleep(Duration::from_millis(10)).await;
+    let status = pool.status();
+    assert_eq!(status.size, 0);
+    assert_eq!(status.recycling, 0);
+    assert_eq!(pool.stats().detached, 1);
+
+    // The slot of the detached object has been given back
+    let _obj0 = pool.get().await.unwrap();
+    let _obj1 = pool.get().await.unwrap();
+    assert_eq!(pool.status().in_use, 2);
+}
+
+#[tokio::test]
+async fn on_return_no_runtime() {
+    let pool = pool(RecycleMode::OnReturn);
+    let obj = pool.get().await.unwrap();
+    std::thread::spawn(move || drop(obj)).join().unwrap();
+    let status = pool.status();
+    assert_eq!(status.size, 0);
+    assert_eq!(status.recycling, 0);
+    assert_eq!(pool.stats().detached, 1);
+    assert_eq!(pool.manager().recycle_count(), 0);
+
+    // The slot of the detached object has been given back
+    let _obj0 = pool.get().await.unwrap();
+    let _obj1 = pool.get().await.unwrap();
+    assert_eq!(pool.status().in_use, 2);
+}
+
+#[tokio::test]
+async fn background() {
+    let pool = pool(RecycleMode::Background {
+        interval: Duration::from_millis(20),
+    });
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.status().available, 1);
+
+    // Returned objects are handed out without being recycled
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.manager().recycle_count(), 0);
+
+    time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(pool.manager().recycle_count(), 1);
+    assert_eq!(pool.status().available, 1);
+
+    // Objects which haven't been used since are left alone
+    time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(pool.manager().recycle_count(), 1);
+}
+
+#[tokio::test]
+async fn background_keeps_used_objects() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .recycle_mode(RecycleMode::Background {
+            interval: Duration::from_secs(10),
+        })
+        .idle_timeout(Some(Duration::from_millis(100)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    for _ in 0..16 {
+        drop(pool.get().await.unwrap());
+        time::sleep(Duration::from_millis(25)).await;
+    }
+    // Objects expire based on the time they were last returned
+    assert_eq!(pool.stats().created, 1);
+}
@@ -0,0 +1,147 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    ptr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use async_trait::async_trait;
+
+use deadpool::managed::{self, Metrics, PauseOptions, PoolError, RecycleResult, SharedObject};
+
+#[derive(Default)]
+struct Manager {
+    create_count: AtomicUsize,
+    recycle_count: AtomicUsize,
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        Ok(self.create_count.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _conn: &mut usize, _: &Metrics) -> RecycleResult<()> {
+        let _ = self.recycle_count.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+type Pool = managed::Pool<Manager>;
+
+fn pool(max_shared_users: usize) -> Pool {
+    Pool::builder(Manager::default())
+        .max_size(4)
+        .max_shared_users(max_shared_users)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn lend_up_to_max_users() {
+    let pool = pool(3);
+    let mut objs = Vec::new();
+    for _ in 0..3 {
+        objs.push(pool.get_shared().await.unwrap());
+    }
+    assert!(objs.iter().all(|obj| **obj == 0));
+    assert_eq!(pool.manager().create_count.load(Ordering::Relaxed), 1);
+    assert_eq!(pool.status().in_use, 1);
+
+    let obj = pool.get_shared().await.unwrap();
+    assert_eq!(*obj, 1);
+    assert_eq!(pool.status().in_use, 2);
+}
+
+#[tokio::test]
+async fn least_loaded() {
+    let pool = pool(2);
+    let obj0 = pool.get_shared().await.unwrap();
+    let _obj1 = pool.get_shared().await.unwrap();
+    let obj2 = pool.get_shared().await.unwrap();
+    assert_eq!(*obj2, 1);
+
+    // Both objects have a single user now
+    drop(obj0);
+    let obj3 = pool.get_shared().await.unwrap();
+    let obj4 = pool.get_shared().await.unwrap();
+    assert_ne!(*obj3, *obj4);
+    assert_eq!(pool.status().in_use, 2);
+}
+
+#[tokio::test]
+async fn recycle_after_last_user() {
+    let pool = pool(2);
+    let obj0 = pool.get_shared().await.unwrap();
+    let obj1 = pool.get_shared().await.unwrap();
+    assert_eq!(**SharedObject::object(&obj0), **SharedObject::object(&obj1));
+
+    drop(obj0);
+    assert_eq!(pool.status().in_use, 1);
+    assert_eq!(pool.status().available, 0);
+
+    drop(obj1);
+    assert_eq!(pool.status().in_use, 0);
+    assert_eq!(pool.status().available, 1);
+
+    let obj = pool.get_shared().await.unwrap();
+    assert_eq!(*obj, 0);
+    assert_eq!(pool.manager().recycle_count.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn max_shared_users_default() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(2)
+        .build()
+        .unwrap();
+    let obj0 = pool.get_shared().await.unwrap();
+    let obj1 = pool.get_shared().await.unwrap();
+    assert_ne!(*obj0, *obj1);
+}
+
+#[tokio::test]
+async fn closed() {
+    let pool = pool(2);
+    let _obj = pool.get_shared().await.unwrap();
+    pool.close();
+    assert!(matches!(pool.get_shared().await, Err(PoolError::Closed)));
+}
+
+#[tokio::test]
+async fn paused() {
+    let pool = pool(2);
+    let _obj = pool.get_shared().await.unwrap();
+    pool.pause(PauseOptions {
+        fail_fast: true,
+        ..PauseOptions::default()
+    });
+    assert!(matches!(pool.get_shared().await, Err(PoolError::Paused)));
+
+    pool.resume();
+    assert_eq!(*pool.get_shared().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn invalidate_all() {
+    let pool = pool(2);
+    let obj0 = pool.get_shared().await.unwrap();
+    pool.invalidate_all();
+
+    // The stale object isn't lent anymore and detached once its last user
+    // is done
+    let obj1 = pool.get_shared().await.unwrap();
+    assert_eq!(*obj1, 1);
+    assert!(!ptr::eq(
+        SharedObject::object(&obj0),
+        SharedObject::object(&obj1)
+    ));
+    drop(obj0);
+    assert_eq!(pool.status().size, 1);
+
+    let obj2 = pool.get_shared().await.unwrap();
+    assert_eq!(*obj2, 1);
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use deadpool::managed::{self, Metrics, Object, PoolError, RecycleError, RecycleResult, Timeouts};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    fail_create: AtomicBool,
+    fail_recycle: AtomicBool,
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        if self.fail_create.load(Ordering::Relaxed) {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        if self.fail_recycle.load(Ordering::Relaxed) {
+            Err(RecycleError::Backend(()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[tokio::test]
+async fn counters() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .build()
+        .unwrap();
+    let no_wait = Timeouts {
+        wait: Some(Duration::ZERO),
+        ..pool.timeouts()
+    };
+
+    // create + recycle
+    drop(pool.get().await.unwrap());
+    let obj = pool.get().await.unwrap();
+
+    // wait timeout
+    assert!(matches!(
+        pool.timeout_get(&no_wait).await,
+        Err(PoolError::Timeout(_))
+    ));
+
+    // take
+    let () = Object::take(obj);
+
+    // create error
+    pool.manager().fail_create.store(true, Ordering::Relaxed);
+    assert!(matches!(pool.get().await, Err(PoolError::Backend(()))));
+    pool.manager().fail_create.store(false, Ordering::Relaxed);
+
+    // recycle error followed by a create
+    drop(pool.get().await.unwrap());
+    pool.manager().fail_recycle.store(true, Ordering::Relaxed);
+    drop(pool.get().await.unwrap());
+
+    let stats = pool.stats();
+    assert_eq!(stats.created, 3);
+    assert_eq!(stats.create_errors, 1);
+    assert_eq!(stats.recycled, 1);
+    assert_eq!(stats.recycle_errors, 1);
+    assert_eq!(stats.detached, 1);
+    assert_eq!(stats.taken, 1);
+    assert_eq!(stats.wait_timeouts, 1);
+    assert_eq!(stats.create_timeouts, 0);
+    assert_eq!(stats.recycle_timeouts, 0);
+}
+
+#[tokio::test]
+async fn wait_time() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    let waiting = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get().await.map(drop) })
+    };
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    drop(obj);
+    waiting.await.unwrap().unwrap();
+
+    let stats = pool.stats();
+    assert!(stats.max_wait_time >= Duration::from_millis(20));
+    assert!(stats.wait_time >= stats.max_wait_time);
+}
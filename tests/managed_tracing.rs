@@ -0,0 +1,87 @@
+#![cfg(all(feature = "managed", feature = "tracing"))]
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use tracing::{
+    field::{Field, Visit},
+    span, Subscriber,
+};
+use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer};
+
+use deadpool::managed::{self, Metrics, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _conn: &mut (), _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+/// Layer recording the names of all spans and their `outcome` fields.
+#[derive(Clone, Default)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<String>>>,
+    outcomes: Arc<Mutex<Vec<String>>>,
+}
+
+struct OutcomeVisitor<'a>(&'a Mutex<Vec<String>>);
+
+impl Visit for OutcomeVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "outcome" {
+            self.0.lock().unwrap().push(value.to_string());
+        }
+    }
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Recorder {
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, _id: &span::Id, _ctx: Context<'_, S>) {
+        self.spans
+            .lock()
+            .unwrap()
+            .push(attrs.metadata().name().to_string());
+    }
+    fn on_record(&self, _id: &span::Id, values: &span::Record<'_>, _ctx: Context<'_, S>) {
+        values.record(&mut OutcomeVisitor(&self.outcomes));
+    }
+}
+
+#[tokio::test]
+async fn spans() {
+    let recorder = Recorder::default();
+    let _guard =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+
+    assert_eq!(
+        *recorder.spans.lock().unwrap(),
+        [
+            "timeout_get",
+            "wait",
+            "try_create",
+            "apply",
+            "timeout_get",
+            "wait",
+            "try_recycle",
+            "apply",
+            "apply",
+        ]
+    );
+    assert_eq!(*recorder.outcomes.lock().unwrap(), ["ok", "ok"]);
+}
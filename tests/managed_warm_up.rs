@@ -0,0 +1,112 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use tokio::time;
+
+use deadpool::managed::{self, Hook, Metrics, PoolError, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    next_id: AtomicUsize,
+    fail_odd: bool,
+}
+
+#[async_trait]
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        time::sleep(Duration::from_millis(50)).await;
+        if self.fail_odd && id % 2 == 1 {
+            Err(())
+        } else {
+            Ok(id)
+        }
+    }
+
+    async fn recycle(&self, _conn: &mut usize, _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn concurrent() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(8)
+        .build()
+        .unwrap();
+    let start = Instant::now();
+    let report = pool.warm_up(4).await;
+    assert!(start.elapsed() < Duration::from_millis(150));
+    assert!(report.is_ok());
+    assert_eq!(report.created, 4);
+    let status = pool.status();
+    assert_eq!(status.size, 4);
+    assert_eq!(status.available, 4);
+}
+
+#[tokio::test]
+async fn partial_failure() {
+    let pool = Pool::builder(Manager {
+        fail_odd: true,
+        ..Default::default()
+    })
+    .max_size(8)
+    .build()
+    .unwrap();
+    let report = pool.warm_up(4).await;
+    assert_eq!(report.created, 2);
+    assert_eq!(report.errors.len(), 2);
+    assert!(report
+        .errors
+        .iter()
+        .all(|e| matches!(e, PoolError::Backend(()))));
+    assert_eq!(pool.status().size, 2);
+}
+
+#[tokio::test]
+async fn limited_by_max_size() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(2)
+        .build()
+        .unwrap();
+    let _obj = pool.get().await.unwrap();
+    let report = pool.warm_up(4).await;
+    assert_eq!(report.created, 1);
+    assert_eq!(pool.status().size, 2);
+}
+
+#[tokio::test]
+async fn post_create_hook() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(2)
+        .post_create(Hook::sync_fn(|obj, _| {
+            *obj += 100;
+            Ok(())
+        }))
+        .build()
+        .unwrap();
+    let report = pool.warm_up(1).await;
+    assert_eq!(report.created, 1);
+    assert_eq!(*pool.get().await.unwrap(), 100);
+}
+
+#[tokio::test]
+async fn build_and_warm() {
+    let (pool, report) = Pool::builder(Manager::default())
+        .max_size(4)
+        .build_and_warm(3)
+        .await
+        .unwrap();
+    assert_eq!(report.created, 3);
+    assert_eq!(pool.status().available, 3);
+}
@@ -4,7 +4,7 @@ use std::time::Duration;
 
 use tokio::{task, time};
 
-use deadpool::unmanaged::{Pool, PoolError};
+use deadpool::unmanaged::{Object, Pool, PoolConfig, PoolError};
 
 #[tokio::test]
 async fn basic() {
@@ -13,24 +13,28 @@ async fn basic() {
     let status = pool.status();
     assert_eq!(status.size, 3);
     assert_eq!(status.available, 3);
+    assert_eq!(status.in_use, 0);
 
     let _val0 = pool.get().await;
 
     let status = pool.status();
     assert_eq!(status.size, 3);
     assert_eq!(status.available, 2);
+    assert_eq!(status.in_use, 1);
 
     let _val1 = pool.get().await;
 
     let status = pool.status();
     assert_eq!(status.size, 3);
     assert_eq!(status.available, 1);
+    assert_eq!(status.in_use, 2);
 
     let _val2 = pool.get().await;
 
     let status = pool.status();
     assert_eq!(status.size, 3);
     assert_eq!(status.available, 0);
+    assert_eq!(status.in_use, 3);
 }
 
 #[tokio::test]
@@ -177,3 +181,81 @@ async fn add_timeout() {
 
     assert_eq!(pool.try_remove().unwrap(), 2);
 }
+
+#[tokio::test]
+async fn max_waiting() {
+    let pool = Pool::from_config(&PoolConfig {
+        max_waiting: Some(1),
+        ..PoolConfig::new(1)
+    });
+    pool.add(()).await.unwrap();
+
+    let obj = pool.get().await.unwrap();
+    let waiting = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get().await.map(drop) })
+    };
+    task::yield_now().await;
+    assert!(matches!(pool.get().await, Err(PoolError::QueueFull)));
+
+    drop(obj);
+    waiting.await.unwrap().unwrap();
+    assert!(pool.get().await.is_ok());
+}
+
+#[tokio::test]
+async fn stats() {
+    let pool = Pool::from(vec![1]);
+    let obj = pool.get().await.unwrap();
+    assert!(matches!(pool.try_get(), Err(PoolError::Timeout)));
+    assert!(matches!(
+        pool.timeout_get(Some(Duration::ZERO)).await,
+        Err(PoolError::Timeout)
+    ));
+    assert_eq!(Object::take(obj), 1);
+
+    let stats = pool.stats();
+    assert_eq!(stats.taken, 1);
+    assert_eq!(stats.wait_timeouts, 2);
+    assert_eq!(stats.created, 0);
+}
+
+#[tokio::test]
+async fn get_many() {
+    let pool = Pool::from(vec![1, 2, 3]);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 3);
+
+    let objs = pool.get_many(2).await.unwrap();
+    assert_eq!(objs.iter().map(|obj| **obj).collect::<Vec<_>>(), vec![2, 1]);
+    assert_eq!(pool.status().in_use, 3);
+    drop(objs);
+
+    // Only two objects are available, so none of them is taken
+    assert!(matches!(
+        pool.timeout_get_many(3, Some(Duration::ZERO)).await,
+        Err(PoolError::Timeout)
+    ));
+    assert_eq!(pool.status().available, 2);
+
+    let waiting = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.get_many(3).await.map(|objs| objs.len()) })
+    };
+    task::yield_now().await;
+    drop(obj);
+    assert_eq!(waiting.await.unwrap().unwrap(), 3);
+    assert_eq!(pool.status().available, 3);
+}
+
+#[tokio::test]
+async fn get_many_too_many_objects() {
+    let pool = Pool::new(2);
+    pool.add(1).await.unwrap();
+    pool.add(2).await.unwrap();
+    assert!(matches!(
+        pool.get_many(3).await,
+        Err(PoolError::TooManyObjects)
+    ));
+    assert_eq!(pool.get_many(2).await.unwrap().len(), 2);
+}
@@ -27,6 +27,7 @@ async fn _test_get(runtime: Runtime) {
         max_size: 16,
         timeout: None,
         runtime: Some(runtime),
+        max_waiting: None,
     };
     let pool = Pool::from_config(&cfg);
     assert!(matches!(
@@ -40,6 +41,7 @@ async fn _test_config(runtime: Runtime) {
         max_size: 16,
         timeout: Some(Duration::from_millis(1)),
         runtime: Some(runtime),
+        max_waiting: None,
     };
     let pool = Pool::from_config(&cfg);
     assert!(matches!(pool.get().await, Err(PoolError::Timeout)));